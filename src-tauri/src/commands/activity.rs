@@ -0,0 +1,177 @@
+use crate::commands::cards::Card;
+use crate::commands::columns::fetch_column;
+use crate::db::Database;
+use crate::error::AppError;
+use rusqlite::{Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ActivityKind {
+    Created,
+    Moved,
+    Renamed,
+    Archived,
+    Unarchived,
+}
+
+impl ActivityKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ActivityKind::Created => "created",
+            ActivityKind::Moved => "moved",
+            ActivityKind::Renamed => "renamed",
+            ActivityKind::Archived => "archived",
+            ActivityKind::Unarchived => "unarchived",
+        }
+    }
+
+    fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "created" => Some(ActivityKind::Created),
+            "moved" => Some(ActivityKind::Moved),
+            "renamed" => Some(ActivityKind::Renamed),
+            "archived" => Some(ActivityKind::Archived),
+            "unarchived" => Some(ActivityKind::Unarchived),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityEntry {
+    pub seq: i64,
+    pub card_id: String,
+    pub kind: ActivityKind,
+    pub summary: String,
+    pub created_at: String,
+}
+
+impl ActivityEntry {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let kind: String = row.get(2)?;
+
+        Ok(ActivityEntry {
+            seq: row.get(0)?,
+            card_id: row.get(1)?,
+            kind: ActivityKind::parse(&kind).ok_or_else(|| {
+                rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, kind.into())
+            })?,
+            summary: row.get(3)?,
+            created_at: row.get(4)?,
+        })
+    }
+}
+
+pub(crate) fn record(
+    conn: &Connection,
+    card_id: &str,
+    kind: ActivityKind,
+    summary: &str,
+    now: &str,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO card_activity (card_id, kind, summary, created_at) VALUES (?, ?, ?, ?)",
+        rusqlite::params![card_id, kind.as_str(), summary, now],
+    )?;
+    Ok(())
+}
+
+fn column_name(conn: &Connection, column_id: &str) -> rusqlite::Result<String> {
+    Ok(fetch_column(conn, column_id)?.map_or_else(|| column_id.to_string(), |column| column.name))
+}
+
+/// Records what a user would notice about a card changing from `before` to
+/// `after`. Order-only changes are not worth an entry.
+pub(crate) fn record_changes(conn: &Connection, before: &Card, after: &Card, now: &str) -> rusqlite::Result<()> {
+    if before.column_id != after.column_id {
+        let summary = format!(
+            "Moved from \"{}\" to \"{}\"",
+            column_name(conn, &before.column_id)?,
+            column_name(conn, &after.column_id)?
+        );
+        record(conn, &after.id, ActivityKind::Moved, &summary, now)?;
+    }
+    if before.title != after.title {
+        let summary = format!("Renamed from \"{}\" to \"{}\"", before.title, after.title);
+        record(conn, &after.id, ActivityKind::Renamed, &summary, now)?;
+    }
+    if before.archived != after.archived {
+        let (kind, summary) = if after.archived {
+            (ActivityKind::Archived, "Archived")
+        } else {
+            (ActivityKind::Unarchived, "Unarchived")
+        };
+        record(conn, &after.id, kind, summary, now)?;
+    }
+    Ok(())
+}
+
+/// Records that a card was added to its column.
+pub(crate) fn record_created(conn: &Connection, card: &Card, now: &str) -> rusqlite::Result<()> {
+    let summary = format!("Created in \"{}\"", column_name(conn, &card.column_id)?);
+    record(conn, &card.id, ActivityKind::Created, &summary, now)
+}
+
+pub(crate) fn activity_for_card(conn: &Connection, card_id: &str) -> rusqlite::Result<Vec<ActivityEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT seq, card_id, kind, summary, created_at FROM card_activity WHERE card_id = ? ORDER BY seq DESC",
+    )?;
+
+    let entries = stmt
+        .query_map([card_id], ActivityEntry::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(entries)
+}
+
+/// Everything that has happened to a card, newest first.
+#[tauri::command]
+pub fn get_card_activity(
+    db: tauri::State<'_, Arc<Database>>,
+    card_id: String,
+) -> Result<Vec<ActivityEntry>, AppError> {
+    db.with_connection(|conn| activity_for_card(conn, &card_id))
+        .map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cards::fetch_card;
+    use crate::db::test_helpers::test_helpers::{create_test_db, seed_board, seed_card, seed_column};
+    use chrono::Utc;
+
+    #[test]
+    fn test_record_changes() {
+        let (db, _temp) = create_test_db();
+
+        let (done_id, card_id) = db.with_connection(|conn| {
+            let board = seed_board(conn, "Board")?;
+            let todo = seed_column(conn, &board.id, "To Do", 1.0)?;
+            let done = seed_column(conn, &board.id, "Done", 2.0)?;
+            let card = seed_card(conn, &todo.id, "Task", 1.0)?;
+            Ok((done.id, card.id))
+        }).unwrap();
+
+        let before = db.with_connection(|conn| fetch_card(conn, &card_id)).unwrap().unwrap();
+        let now = Utc::now().to_rfc3339();
+
+        // Reordering within a column leaves no trace
+        let reordered = Card { order: 5.0, ..before.clone() };
+        db.with_connection(|conn| record_changes(conn, &before, &reordered, &now)).unwrap();
+        assert!(db.with_connection(|conn| activity_for_card(conn, &card_id)).unwrap().is_empty());
+
+        let after = Card { column_id: done_id, title: "Finished task".to_string(), archived: true, ..before.clone() };
+        db.with_connection(|conn| record_changes(conn, &before, &after, &now)).unwrap();
+
+        let entries = db.with_connection(|conn| activity_for_card(conn, &card_id)).unwrap();
+        let kinds: Vec<_> = entries.iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, vec![ActivityKind::Archived, ActivityKind::Renamed, ActivityKind::Moved]);
+        assert_eq!(entries[2].summary, "Moved from \"To Do\" to \"Done\"");
+        assert_eq!(entries[1].summary, "Renamed from \"Task\" to \"Finished task\"");
+        assert!(entries.iter().all(|e| e.card_id == card_id));
+    }
+}
@@ -0,0 +1,447 @@
+use crate::commands::cards::fetch_card;
+use crate::db::{Database, DbError};
+use crate::error::AppError;
+use crate::validation::Limits;
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri_plugin_opener::OpenerExt;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    pub id: String,
+    pub card_id: String,
+    /// Name of the file when it was attached, used again when opening it.
+    pub file_name: String,
+    /// SHA-256 of the contents, which is also the file's name in the store.
+    pub hash: String,
+    pub size: i64,
+    pub created_at: String,
+}
+
+/// Column list matching `Attachment::from_row`.
+pub(crate) const ATTACHMENT_COLUMNS: &str = "id, card_id, file_name, hash, size, created_at";
+
+impl Attachment {
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Attachment {
+            id: row.get(0)?,
+            card_id: row.get(1)?,
+            file_name: row.get(2)?,
+            hash: row.get(3)?,
+            size: row.get(4)?,
+            created_at: row.get(5)?,
+        })
+    }
+}
+
+/// Content-addressed file storage in the app data directory. Files are named
+/// by the hash of their contents and never change once written, so identical
+/// files are stored once and can be hard-linked into backups.
+pub struct AttachmentStore {
+    root: PathBuf,
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// The name a file with these contents is stored under.
+pub(crate) fn content_hash(data: &[u8]) -> String {
+    hex(&Sha256::digest(data))
+}
+
+fn is_hash(name: &str) -> bool {
+    name.len() == 64 && name.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+impl AttachmentStore {
+    pub fn new(root: PathBuf) -> Self {
+        AttachmentStore { root }
+    }
+
+    pub(crate) fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path_for(&self, hash: &str) -> PathBuf {
+        self.root.join(&hash[..2]).join(hash)
+    }
+
+    fn temp_path(&self) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.root)?;
+        Ok(self.root.join(format!(".{}.tmp", Uuid::new_v4())))
+    }
+
+    /// Moves a fully written temp file into place, or drops it if the
+    /// content is already stored.
+    fn commit(&self, temp: &Path, hash: &str) -> io::Result<()> {
+        let path = self.path_for(hash);
+        if path.exists() {
+            return fs::remove_file(temp);
+        }
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::rename(temp, path)
+    }
+
+    /// Copies a file into the store. Returns its hash and size.
+    pub(crate) fn put_file(&self, source: &Path) -> io::Result<(String, u64)> {
+        let mut input = File::open(source)?;
+        let temp = self.temp_path()?;
+
+        let copied = (|| {
+            let mut output = File::create(&temp)?;
+            let mut hasher = Sha256::new();
+            let mut buf = vec![0; 64 * 1024];
+            let mut size = 0;
+            loop {
+                let n = input.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                hasher.update(&buf[..n]);
+                output.write_all(&buf[..n])?;
+                size += n as u64;
+            }
+            output.sync_all()?;
+            Ok((hex(&hasher.finalize()), size))
+        })();
+
+        match copied.and_then(|(hash, size)| self.commit(&temp, &hash).map(|_| (hash, size))) {
+            Ok(stored) => Ok(stored),
+            Err(e) => {
+                let _ = fs::remove_file(&temp);
+                Err(e)
+            }
+        }
+    }
+
+    /// Stores `data` and returns its hash.
+    pub(crate) fn put_bytes(&self, data: &[u8]) -> io::Result<String> {
+        let hash = content_hash(data);
+        if !self.path_for(&hash).exists() {
+            let temp = self.temp_path()?;
+            if let Err(e) = fs::write(&temp, data).and_then(|_| self.commit(&temp, &hash)) {
+                let _ = fs::remove_file(&temp);
+                return Err(e);
+            }
+        }
+        Ok(hash)
+    }
+
+    pub(crate) fn read(&self, hash: &str) -> io::Result<Vec<u8>> {
+        fs::read(self.path_for(hash))
+    }
+
+    fn remove(&self, hash: &str) -> io::Result<()> {
+        match fs::remove_file(self.path_for(hash)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Hashes of every stored file. Leftover temp files are skipped.
+    fn hashes(&self) -> io::Result<Vec<String>> {
+        let mut hashes = Vec::new();
+        if !self.root.exists() {
+            return Ok(hashes);
+        }
+
+        for dir in fs::read_dir(&self.root)? {
+            let dir = dir?;
+            if !dir.file_type()?.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(dir.path())? {
+                let name = file?.file_name().to_string_lossy().to_string();
+                if is_hash(&name) {
+                    hashes.push(name);
+                }
+            }
+        }
+        Ok(hashes)
+    }
+
+    /// Links every stored file into the store rooted at `dest`, skipping ones
+    /// it already has. Falls back to copying where hard links aren't possible.
+    pub(crate) fn copy_to(&self, dest: &Path) -> io::Result<()> {
+        let target = AttachmentStore::new(dest.to_path_buf());
+        for hash in self.hashes()? {
+            let to = target.path_for(&hash);
+            if to.exists() {
+                continue;
+            }
+            fs::create_dir_all(to.parent().unwrap())?;
+            let from = self.path_for(&hash);
+            if fs::hard_link(&from, &to).is_err() {
+                fs::copy(&from, &to)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn fetch_attachment(conn: &Connection, id: &str) -> rusqlite::Result<Option<Attachment>> {
+    conn.query_row(
+        &format!("SELECT {ATTACHMENT_COLUMNS} FROM card_attachments WHERE id = ?"),
+        [id],
+        Attachment::from_row,
+    )
+    .optional()
+}
+
+pub(crate) fn insert_attachment(conn: &Connection, attachment: &Attachment) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO card_attachments (id, card_id, file_name, hash, size, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+        rusqlite::params![
+            &attachment.id,
+            &attachment.card_id,
+            &attachment.file_name,
+            &attachment.hash,
+            attachment.size,
+            &attachment.created_at
+        ],
+    )?;
+    Ok(())
+}
+
+pub(crate) fn attachments_for_card(conn: &Connection, card_id: &str) -> rusqlite::Result<Vec<Attachment>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {ATTACHMENT_COLUMNS} FROM card_attachments WHERE card_id = ? ORDER BY created_at ASC, id ASC"
+    ))?;
+
+    let attachments = stmt
+        .query_map([card_id], Attachment::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(attachments)
+}
+
+/// Attaches a file that `put_file` already copied into the store, naming it
+/// after `source`. The copy happens before the transaction so the database
+/// isn't locked while it runs; if attaching fails, `collect_garbage` cleans
+/// up the stored file.
+pub(crate) fn attach(
+    conn: &Connection,
+    card_id: &str,
+    source: &Path,
+    (hash, size): (String, u64),
+    now: &str,
+) -> Result<Attachment, DbError> {
+    if fetch_card(conn, card_id)?.is_none() {
+        return Err(DbError::NotFound { kind: "card", id: card_id.to_string() });
+    }
+
+    let attachment = Attachment {
+        id: Uuid::new_v4().to_string(),
+        card_id: card_id.to_string(),
+        file_name: source
+            .file_name()
+            .map_or_else(|| "attachment".to_string(), |name| name.to_string_lossy().to_string()),
+        hash,
+        size: size as i64,
+        created_at: now.to_string(),
+    };
+
+    insert_attachment(conn, &attachment)?;
+    Ok(attachment)
+}
+
+/// Detaches a file. Returns `false` if there was no such attachment. The
+/// stored file is left for `collect_garbage`, so a rolled back transaction
+/// never points at a deleted file.
+pub(crate) fn detach(conn: &Connection, id: &str) -> Result<bool, DbError> {
+    Ok(conn.execute("DELETE FROM card_attachments WHERE id = ?", [id])? > 0)
+}
+
+/// Deletes stored files that no attachment refers to any more, e.g. after
+/// their cards were purged from the trash. Returns how many were removed.
+pub(crate) fn collect_garbage(conn: &Connection, store: &AttachmentStore) -> Result<usize, DbError> {
+    let referenced = conn
+        .prepare("SELECT DISTINCT hash FROM card_attachments")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<HashSet<String>, _>>()?;
+
+    let mut removed = 0;
+    for hash in store.hashes()? {
+        if !referenced.contains(&hash) {
+            store.remove(&hash)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[tauri::command]
+pub fn attach_file(
+    db: tauri::State<'_, Arc<Database>>,
+    store: tauri::State<'_, AttachmentStore>,
+    limits: tauri::State<'_, Limits>,
+    card_id: String,
+    source_path: String,
+) -> Result<Attachment, AppError> {
+    let source = Path::new(&source_path);
+    let size = fs::metadata(source)?.len();
+    if size > limits.max_attachment_bytes {
+        return Err(AppError::Validation(format!(
+            "File is larger than {} MB",
+            limits.max_attachment_bytes / (1024 * 1024)
+        )));
+    }
+    let now = Utc::now().to_rfc3339();
+    let stored = store.put_file(source)?;
+
+    db.with_transaction(|tx| attach(tx, &card_id, source, stored, &now))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn list_attachments(
+    db: tauri::State<'_, Arc<Database>>,
+    card_id: String,
+) -> Result<Vec<Attachment>, AppError> {
+    db.with_connection(|conn| attachments_for_card(conn, &card_id))
+        .map_err(AppError::from)
+}
+
+/// Open an attachment with the system's default app. The file is opened from
+/// a temporary copy under its original name, so edits aren't saved back.
+#[tauri::command]
+pub fn open_attachment(
+    app: tauri::AppHandle,
+    db: tauri::State<'_, Arc<Database>>,
+    store: tauri::State<'_, AttachmentStore>,
+    id: String,
+) -> Result<(), AppError> {
+    let attachment = db
+        .with_connection(|conn| fetch_attachment(conn, &id))?
+        .ok_or_else(|| AppError::NotFound(format!("Attachment {} not found", id)))?;
+
+    let file_name = Path::new(&attachment.file_name)
+        .file_name()
+        .map_or_else(|| attachment.hash.clone().into(), |name| name.to_os_string());
+    let dir = std::env::temp_dir().join("kanban-attachments").join(&attachment.id);
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(file_name);
+    fs::write(&path, store.read(&attachment.hash)?)?;
+
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| AppError::Io(e.to_string()))
+}
+
+#[tauri::command]
+pub fn remove_attachment(
+    db: tauri::State<'_, Arc<Database>>,
+    id: String,
+) -> Result<(), AppError> {
+    db.with_transaction(|tx| {
+        if !detach(tx, &id)? {
+            return Err(DbError::NotFound { kind: "attachment", id: id.clone() });
+        }
+        Ok(())
+    })
+    .map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_helpers::test_helpers::{create_test_db, seed_board_with_card};
+
+    fn seed_card(conn: &Connection) -> rusqlite::Result<String> {
+        let (_, _, card) = seed_board_with_card(conn)?;
+        Ok(card.id)
+    }
+
+    #[test]
+    fn test_identical_files_are_stored_once() {
+        let (db, _temp) = create_test_db();
+        let dir = tempfile::tempdir().unwrap();
+        let store = AttachmentStore::new(dir.path().join("attachments"));
+
+        let card_id = db.with_connection(seed_card).unwrap();
+        let first = dir.path().join("spec.pdf");
+        let second = dir.path().join("spec-copy.pdf");
+        fs::write(&first, b"same bytes").unwrap();
+        fs::write(&second, b"same bytes").unwrap();
+
+        let now = Utc::now().to_rfc3339();
+        let stored = store.put_file(&first).unwrap();
+        let a = db.with_transaction(|tx| attach(tx, &card_id, &first, stored, &now)).unwrap();
+        let stored = store.put_file(&second).unwrap();
+        let b = db.with_transaction(|tx| attach(tx, &card_id, &second, stored, &now)).unwrap();
+
+        assert_eq!(a.hash, b.hash);
+        assert_eq!(a.size, 10);
+        assert_eq!(b.file_name, "spec-copy.pdf");
+        assert_eq!(store.hashes().unwrap(), vec![a.hash.clone()]);
+        assert_eq!(store.read(&a.hash).unwrap(), b"same bytes");
+
+        let listed = db.with_connection(|conn| attachments_for_card(conn, &card_id)).unwrap();
+        assert_eq!(listed.len(), 2);
+
+        // The file stays until the last attachment using it is removed
+        assert!(db.with_transaction(|tx| detach(tx, &a.id)).unwrap());
+        assert_eq!(db.with_transaction(|tx| collect_garbage(tx, &store)).unwrap(), 0);
+        assert_eq!(store.read(&b.hash).unwrap(), b"same bytes");
+        assert!(db.with_transaction(|tx| detach(tx, &b.id)).unwrap());
+        assert_eq!(db.with_transaction(|tx| collect_garbage(tx, &store)).unwrap(), 1);
+        assert!(store.hashes().unwrap().is_empty());
+        assert!(!db.with_transaction(|tx| detach(tx, &b.id)).unwrap());
+    }
+
+    #[test]
+    fn test_attach_to_missing_card_fails() {
+        let (db, _temp) = create_test_db();
+        let dir = tempfile::tempdir().unwrap();
+        let store = AttachmentStore::new(dir.path().join("attachments"));
+
+        let source = dir.path().join("notes.txt");
+        fs::write(&source, b"notes").unwrap();
+
+        let now = Utc::now().to_rfc3339();
+        let stored = store.put_file(&source).unwrap();
+        let err = db.with_transaction(|tx| attach(tx, "missing", &source, stored, &now)).unwrap_err();
+        assert!(matches!(err, DbError::NotFound { kind: "card", .. }));
+
+        // The copy made before the transaction is cleaned up later
+        assert_eq!(db.with_transaction(|tx| collect_garbage(tx, &store)).unwrap(), 1);
+        assert!(store.hashes().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_garbage_collection_and_copy() {
+        let (db, _temp) = create_test_db();
+        let dir = tempfile::tempdir().unwrap();
+        let store = AttachmentStore::new(dir.path().join("attachments"));
+
+        let card_id = db.with_connection(seed_card).unwrap();
+        let source = dir.path().join("keep.txt");
+        fs::write(&source, b"keep me").unwrap();
+        let now = Utc::now().to_rfc3339();
+        let stored = store.put_file(&source).unwrap();
+        let kept = db.with_transaction(|tx| attach(tx, &card_id, &source, stored, &now)).unwrap();
+        let orphan = store.put_bytes(b"nobody uses me").unwrap();
+
+        let backup = dir.path().join("backup.attachments");
+        store.copy_to(&backup).unwrap();
+        let mut copied = AttachmentStore::new(backup).hashes().unwrap();
+        copied.sort();
+        let mut expected = vec![kept.hash.clone(), orphan.clone()];
+        expected.sort();
+        assert_eq!(copied, expected);
+
+        let removed = db.with_transaction(|tx| collect_garbage(tx, &store)).unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(store.hashes().unwrap(), vec![kept.hash]);
+    }
+}
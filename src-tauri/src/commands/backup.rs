@@ -1,45 +1,143 @@
+use crate::commands::attachments::AttachmentStore;
+use crate::db::Database;
+use crate::error::AppError;
 use chrono::Utc;
+use rusqlite::Connection;
+use std::collections::HashMap;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
 use tauri::Manager;
 
+/// Where the attachment files belonging to a backup are kept, next to the
+/// backup itself (`kanban_backup_X.db` -> `kanban_backup_X.attachments`).
+pub(crate) fn attachments_backup_dir(backup_path: &Path) -> PathBuf {
+    backup_path.with_extension("attachments")
+}
+
+/// Removes a backup along with its attachment files.
+pub(crate) fn remove_backup(backup_path: &Path) -> std::io::Result<()> {
+    fs::remove_file(backup_path)?;
+    let _ = fs::remove_dir_all(attachments_backup_dir(backup_path));
+    Ok(())
+}
+
+/// A fresh path in `backups_dir` for a backup of the given kind, e.g.
+/// `kanban_backup_20260101_120000_123.db`. A counter is appended if two
+/// backups land in the same millisecond.
+pub(crate) fn new_backup_path(backups_dir: &Path, kind: &str) -> PathBuf {
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S_%3f");
+    let mut path = backups_dir.join(format!("kanban_{}_{}.db", kind, timestamp));
+    let mut n = 1;
+    while path.exists() {
+        path = backups_dir.join(format!("kanban_{}_{}_{}.db", kind, timestamp, n));
+        n += 1;
+    }
+    path
+}
+
+/// Deletes all but the newest `keep_count` backups of each kind (`backup`,
+/// `prerestore`, `startup`), so one kind never pushes out another. Age is
+/// taken from the file's modification time. Returns how many were deleted.
+pub(crate) fn prune_backups(backups_dir: &Path, keep_count: usize) -> std::io::Result<usize> {
+    let mut by_kind: HashMap<String, Vec<(SystemTime, PathBuf)>> = HashMap::new();
+    for entry in fs::read_dir(backups_dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "db") {
+            continue;
+        }
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        let kind = name.split('_').take(2).collect::<Vec<_>>().join("_");
+        let modified = fs::metadata(&path)?.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        by_kind.entry(kind).or_default().push((modified, path));
+    }
+
+    let mut deleted = 0;
+    for mut backups in by_kind.into_values() {
+        // Newest first
+        backups.sort_by(|a, b| b.cmp(a));
+        for (_, path) in backups.into_iter().skip(keep_count) {
+            if remove_backup(&path).is_ok() {
+                deleted += 1;
+                // Also remove WAL and SHM files left by older file-copy backups
+                let _ = fs::remove_file(format!("{}-wal", path.display()));
+                let _ = fs::remove_file(format!("{}-shm", path.display()));
+            }
+        }
+    }
+    Ok(deleted)
+}
+
+/// Write an online backup of the live database and verify it before reporting success.
+/// A backup that fails verification is deleted rather than left looking usable.
+/// Attachment files are linked in alongside, so the backup is complete on its own.
+pub(crate) fn write_verified_backup(
+    db: &Database,
+    store: &AttachmentStore,
+    backup_path: &Path,
+) -> Result<(), AppError> {
+    db.backup_to(backup_path)?;
+
+    let verified = verify_backup(backup_path)
+        .and_then(|_| Ok(store.copy_to(&attachments_backup_dir(backup_path))?));
+    if let Err(e) = verified {
+        let _ = remove_backup(backup_path);
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Check that a file is an intact kanban database before we trust it
+pub(crate) fn verify_backup(path: &Path) -> Result<(), AppError> {
+    let conn = Connection::open(path)?;
+
+    let result: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|e| AppError::Corruption(format!("Backup is not a readable database: {}", e)))?;
+    if result != "ok" {
+        return Err(AppError::Corruption(format!("Backup failed integrity check: {}", result)));
+    }
+
+    let has_boards: bool = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'boards')",
+            [],
+            |row| row.get(0),
+        )?;
+    if !has_boards {
+        return Err(AppError::Corruption("Backup is not a kanban database".to_string()));
+    }
+
+    Ok(())
+}
+
 /// Create a backup of the database
 #[tauri::command]
-pub fn create_backup(app: tauri::AppHandle) -> Result<String, String> {
-    let app_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
-    let db_path = app_dir.join("kanban.db");
+pub fn create_backup(
+    app: tauri::AppHandle,
+    db: tauri::State<'_, Arc<Database>>,
+    store: tauri::State<'_, AttachmentStore>,
+) -> Result<String, AppError> {
+    let app_dir = app.path().app_data_dir().map_err(|e| AppError::Io(e.to_string()))?;
     let backups_dir = app_dir.join("backups");
 
     // Create backups directory if it doesn't exist
-    fs::create_dir_all(&backups_dir).map_err(|e| e.to_string())?;
+    fs::create_dir_all(&backups_dir)?;
 
     // Generate backup filename with timestamp
-    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-    let backup_filename = format!("kanban_backup_{}.db", timestamp);
-    let backup_path = backups_dir.join(&backup_filename);
-
-    // Copy database file
-    if db_path.exists() {
-        fs::copy(&db_path, &backup_path).map_err(|e| e.to_string())?;
+    let backup_path = new_backup_path(&backups_dir, "backup");
 
-        // Also copy WAL and SHM files if they exist
-        let wal_path = db_path.with_extension("db-wal");
-        let shm_path = db_path.with_extension("db-shm");
-
-        if wal_path.exists() {
-            let _ = fs::copy(&wal_path, backup_path.with_extension("db-wal"));
-        }
-        if shm_path.exists() {
-            let _ = fs::copy(&shm_path, backup_path.with_extension("db-shm"));
-        }
-    }
+    write_verified_backup(&db, &store, &backup_path)?;
 
     Ok(backup_path.to_string_lossy().to_string())
 }
 
 /// List available backups
 #[tauri::command]
-pub fn list_backups(app: tauri::AppHandle) -> Result<Vec<BackupInfo>, String> {
-    let app_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+pub fn list_backups(app: tauri::AppHandle) -> Result<Vec<BackupInfo>, AppError> {
+    let app_dir = app.path().app_data_dir().map_err(|e| AppError::Io(e.to_string()))?;
     let backups_dir = app_dir.join("backups");
 
     if !backups_dir.exists() {
@@ -48,13 +146,13 @@ pub fn list_backups(app: tauri::AppHandle) -> Result<Vec<BackupInfo>, String> {
 
     let mut backups = Vec::new();
 
-    for entry in fs::read_dir(&backups_dir).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
+    for entry in fs::read_dir(&backups_dir)? {
+        let entry = entry?;
         let path = entry.path();
 
-        if path.extension().map_or(false, |ext| ext == "db") {
+        if path.extension().is_some_and(|ext| ext == "db") {
             let filename = path.file_name().unwrap().to_string_lossy().to_string();
-            let metadata = fs::metadata(&path).map_err(|e| e.to_string())?;
+            let metadata = fs::metadata(&path)?;
             let size = metadata.len();
 
             backups.push(BackupInfo {
@@ -71,38 +169,62 @@ pub fn list_backups(app: tauri::AppHandle) -> Result<Vec<BackupInfo>, String> {
     Ok(backups)
 }
 
-/// Clean up old backups, keeping only the most recent N
+/// Clean up old backups, keeping only the most recent N of each kind
 #[tauri::command]
-pub fn cleanup_old_backups(app: tauri::AppHandle, keep_count: usize) -> Result<usize, String> {
-    let backups = list_backups(app.clone())?;
+pub fn cleanup_old_backups(app: tauri::AppHandle, keep_count: usize) -> Result<usize, AppError> {
+    let app_dir = app.path().app_data_dir().map_err(|e| AppError::Io(e.to_string()))?;
+    let backups_dir = app_dir.join("backups");
 
-    if backups.len() <= keep_count {
+    if !backups_dir.exists() {
         return Ok(0);
     }
+    Ok(prune_backups(&backups_dir, keep_count)?)
+}
 
-    let mut deleted = 0;
-    for backup in backups.iter().skip(keep_count) {
-        if fs::remove_file(&backup.path).is_ok() {
-            deleted += 1;
-            // Also remove WAL and SHM files
-            let _ = fs::remove_file(format!("{}-wal", backup.path));
-            let _ = fs::remove_file(format!("{}-shm", backup.path));
-        }
+/// Restore the database from a backup, snapshotting the current state first
+#[tauri::command]
+pub fn restore_backup(
+    app: tauri::AppHandle,
+    db: tauri::State<'_, Arc<Database>>,
+    store: tauri::State<'_, AttachmentStore>,
+    filename: String,
+) -> Result<String, AppError> {
+    let app_dir = app.path().app_data_dir().map_err(|e| AppError::Io(e.to_string()))?;
+    let backups_dir = app_dir.join("backups");
+
+    // Only accept plain file names from the backups directory
+    let is_plain_name = Path::new(&filename).file_name().is_some_and(|name| name == filename.as_str());
+    if !is_plain_name || !filename.ends_with(".db") {
+        return Err(AppError::Validation(format!("Invalid backup name: {}", filename)));
     }
 
-    Ok(deleted)
+    let backup_path = backups_dir.join(&filename);
+    if !backup_path.exists() {
+        return Err(AppError::NotFound(format!("Backup not found: {}", filename)));
+    }
+    verify_backup(&backup_path)?;
+
+    // Keep the current database so the restore itself can be undone
+    let snapshot_path = new_backup_path(&backups_dir, "prerestore");
+    write_verified_backup(&db, &store, &snapshot_path)?;
+
+    db.replace_with(&backup_path)?;
+    // Files are never overwritten, so this only adds what the backup refers to
+    AttachmentStore::new(attachments_backup_dir(&backup_path)).copy_to(store.root())?;
+
+    Ok(snapshot_path.to_string_lossy().to_string())
 }
 
 /// Check database integrity
 #[tauri::command]
 pub fn check_database_integrity(
     db: tauri::State<'_, std::sync::Arc<crate::db::Database>>,
-) -> Result<bool, String> {
+) -> Result<bool, AppError> {
     db.with_connection(|conn| {
         let result: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
         Ok(result == "ok")
     })
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
 }
 
 #[derive(serde::Serialize)]
@@ -112,3 +234,174 @@ pub struct BackupInfo {
     pub path: String,
     pub size: u64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_helpers::test_helpers::{create_test_db, seed_board};
+    use tempfile::NamedTempFile;
+
+    fn board_names(db: &Database) -> Vec<String> {
+        db.with_connection(|conn| {
+            let mut stmt = conn.prepare("SELECT name FROM boards ORDER BY name")?;
+            let names = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()?;
+            Ok(names)
+        }).unwrap()
+    }
+
+    fn insert_board(db: &Database, name: &str) {
+        db.with_connection(|conn| seed_board(conn, name)).unwrap();
+    }
+
+    #[test]
+    fn test_verify_backup() {
+        let (backup, backup_temp) = create_test_db();
+        drop(backup);
+        assert!(verify_backup(backup_temp.path()).is_ok());
+
+        let garbage = NamedTempFile::new().unwrap();
+        fs::write(garbage.path(), b"definitely not sqlite, just some bytes padding it out").unwrap();
+        assert!(verify_backup(garbage.path()).is_err());
+
+        let empty = NamedTempFile::new().unwrap();
+        let err = verify_backup(empty.path()).unwrap_err();
+        assert!(matches!(err, AppError::Corruption(ref message) if message == "Backup is not a kanban database"));
+    }
+
+    #[test]
+    fn test_backup_includes_uncheckpointed_writes() {
+        let (db, _temp) = create_test_db();
+        insert_board(&db, "Only in WAL");
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = AttachmentStore::new(dir.path().join("attachments"));
+        let backup_path = dir.path().join("kanban_backup_test.db");
+        write_verified_backup(&db, &store, &backup_path).unwrap();
+
+        // No WAL sidecar needed
+        assert!(!dir.path().join("kanban_backup_test.db-wal").exists());
+
+        let backup = Database::new(&backup_path).unwrap();
+        assert_eq!(board_names(&backup), vec!["Only in WAL"]);
+    }
+
+    #[test]
+    fn test_backup_includes_attachment_files() {
+        let (db, _temp) = create_test_db();
+        let dir = tempfile::tempdir().unwrap();
+        let store = AttachmentStore::new(dir.path().join("attachments"));
+        let hash = store.put_bytes(b"attached").unwrap();
+
+        let backup_path = dir.path().join("kanban_backup_test.db");
+        write_verified_backup(&db, &store, &backup_path).unwrap();
+
+        let backed_up = AttachmentStore::new(attachments_backup_dir(&backup_path));
+        assert_eq!(backed_up.read(&hash).unwrap(), b"attached");
+
+        remove_backup(&backup_path).unwrap();
+        assert!(!attachments_backup_dir(&backup_path).exists());
+    }
+
+    #[test]
+    fn test_backup_refuses_to_overwrite() {
+        let (db, _temp) = create_test_db();
+        let dir = tempfile::tempdir().unwrap();
+        let store = AttachmentStore::new(dir.path().join("attachments"));
+        let existing = NamedTempFile::new().unwrap();
+        fs::write(existing.path(), b"an earlier backup").unwrap();
+
+        assert!(write_verified_backup(&db, &store, existing.path()).is_err());
+        assert_eq!(fs::read(existing.path()).unwrap(), b"an earlier backup");
+    }
+
+    #[test]
+    fn test_replace_with_swaps_live_connection() {
+        let (backup, backup_temp) = create_test_db();
+        insert_board(&backup, "From backup");
+        drop(backup);
+
+        let (db, _temp) = create_test_db();
+        insert_board(&db, "Current");
+
+        db.replace_with(backup_temp.path()).unwrap();
+        assert_eq!(board_names(&db), vec!["From backup"]);
+
+        // The swapped-in connection is fully usable
+        insert_board(&db, "After restore");
+        assert_eq!(board_names(&db), vec!["After restore", "From backup"]);
+    }
+
+    #[test]
+    fn test_replace_with_keeps_original_on_failure() {
+        let (db, _temp) = create_test_db();
+        insert_board(&db, "Current");
+
+        assert!(db.replace_with(Path::new("/nonexistent/backup.db")).is_err());
+        assert_eq!(board_names(&db), vec!["Current"]);
+    }
+
+    #[test]
+    fn test_replace_with_reopens_original_when_restored_copy_cannot_open() {
+        let (db, temp) = create_test_db();
+        insert_board(&db, "Current");
+
+        let garbage = NamedTempFile::new().unwrap();
+        fs::write(garbage.path(), b"definitely not sqlite, just some bytes padding it out").unwrap();
+        assert!(db.replace_with(garbage.path()).is_err());
+        assert_eq!(board_names(&db), vec!["Current"]);
+
+        // Writes still reach the file on disk, not a stand-in connection
+        insert_board(&db, "After failed restore");
+        drop(db);
+        let reopened = Database::new(temp.path()).unwrap();
+        assert_eq!(board_names(&reopened), vec!["After failed restore", "Current"]);
+    }
+
+    #[test]
+    fn test_backup_names_are_unique() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let first = new_backup_path(dir.path(), "prerestore");
+        fs::write(&first, b"").unwrap();
+        let second = new_backup_path(dir.path(), "prerestore");
+        assert_ne!(first, second);
+        assert!(second.file_name().unwrap().to_string_lossy().starts_with("kanban_prerestore_"));
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_of_each_kind() {
+        let dir = tempfile::tempdir().unwrap();
+        let start = SystemTime::now() - std::time::Duration::from_secs(3600);
+
+        // Names sort differently from age on purpose
+        let files = [
+            ("kanban_startup_1.db", 1),
+            ("kanban_startup_2.db", 4),
+            ("kanban_startup_3.db", 3),
+            ("kanban_prerestore_9.db", 2),
+            ("kanban_backup_5.db", 5),
+        ];
+        for (name, minute) in files {
+            let path = dir.path().join(name);
+            fs::write(&path, b"").unwrap();
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(start + std::time::Duration::from_secs(60 * minute))
+                .unwrap();
+        }
+
+        assert_eq!(prune_backups(dir.path(), 2).unwrap(), 1);
+        assert!(!dir.path().join("kanban_startup_1.db").exists());
+        for name in ["kanban_startup_2.db", "kanban_startup_3.db", "kanban_prerestore_9.db", "kanban_backup_5.db"] {
+            assert!(dir.path().join(name).exists(), "{} was pruned", name);
+        }
+
+        assert_eq!(prune_backups(dir.path(), 1).unwrap(), 1);
+        assert!(dir.path().join("kanban_startup_2.db").exists());
+        assert!(!dir.path().join("kanban_startup_3.db").exists());
+    }
+}
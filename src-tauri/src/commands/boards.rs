@@ -1,7 +1,15 @@
-use crate::db::Database;
+use crate::commands::cards::{cards_for_board, Card};
+use crate::commands::columns::{columns_for_board, insert_column, Column, WipPolicy};
+use crate::commands::history::{self, Op};
+use crate::commands::lanes::{lanes_for_board, Lane};
+use crate::commands::trash::{self, TrashKind};
+use crate::db::{Database, DbError};
+use crate::error::AppError;
+use crate::validation::{Limits, Validate};
 use chrono::Utc;
-use rusqlite::OptionalExtension;
+use rusqlite::{Connection, OptionalExtension, Row};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -15,10 +23,165 @@ pub struct Board {
     pub updated_at: String,
 }
 
+/// Column list matching `Board::from_row`.
+pub(crate) const BOARD_COLUMNS: &str = "id, name, last_opened_at, created_at, updated_at";
+
+impl Board {
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Board {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            last_opened_at: row.get(2)?,
+            created_at: row.get(3)?,
+            updated_at: row.get(4)?,
+        })
+    }
+}
+
+pub(crate) fn fetch_board(conn: &Connection, id: &str) -> rusqlite::Result<Option<Board>> {
+    conn.query_row(
+        &format!("SELECT {BOARD_COLUMNS} FROM boards WHERE id = ?"),
+        [id],
+        Board::from_row,
+    )
+    .optional()
+}
+
+/// Like `fetch_board`, but skips boards in the trash.
+pub(crate) fn fetch_live_board(conn: &Connection, id: &str) -> rusqlite::Result<Option<Board>> {
+    conn.query_row(
+        &format!("SELECT {BOARD_COLUMNS} FROM boards WHERE id = ? AND deleted_at IS NULL"),
+        [id],
+        Board::from_row,
+    )
+    .optional()
+}
+
+pub(crate) fn insert_board(conn: &Connection, board: &Board) -> rusqlite::Result<()> {
+    write_board(conn, board, "")
+}
+
+/// Inserts the board or overwrites the existing row in place.
+pub(crate) fn upsert_board(conn: &Connection, board: &Board) -> rusqlite::Result<()> {
+    write_board(
+        conn,
+        board,
+        "ON CONFLICT(id) DO UPDATE SET name = excluded.name, last_opened_at = excluded.last_opened_at,
+             created_at = excluded.created_at, updated_at = excluded.updated_at",
+    )
+}
+
+fn write_board(conn: &Connection, board: &Board, on_conflict: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            "INSERT INTO boards (id, name, last_opened_at, created_at, updated_at) VALUES (?, ?, ?, ?, ?) {on_conflict}"
+        ),
+        rusqlite::params![
+            &board.id,
+            &board.name,
+            &board.last_opened_at,
+            &board.created_at,
+            &board.updated_at
+        ],
+    )?;
+    Ok(())
+}
+
+/// Adds a preset's columns to a freshly created board, in order.
+pub(crate) fn insert_preset_columns(
+    conn: &Connection,
+    board_id: &str,
+    preset: BoardPreset,
+    now: &str,
+) -> rusqlite::Result<Vec<Column>> {
+    let names = preset.column_names();
+    let mut columns = Vec::new();
+    for (i, name) in names.iter().enumerate() {
+        let column = Column {
+            id: Uuid::new_v4().to_string(),
+            board_id: board_id.to_string(),
+            name: name.to_string(),
+            order: (i + 1) as f64,
+            archived: false,
+            created_at: now.to_string(),
+            updated_at: now.to_string(),
+            wip_limit: None,
+            wip_policy: WipPolicy::default(),
+            // Every preset ends with its "Done" column
+            done: i + 1 == names.len(),
+        };
+        insert_column(conn, &column)?;
+        columns.push(column);
+    }
+    Ok(columns)
+}
+
+/// Everything needed to render a board, read in one transaction.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardSnapshot {
+    pub board: Board,
+    pub columns: Vec<Column>,
+    pub lanes: Vec<Lane>,
+    /// Cards keyed by column id, in display order (by lane, then position).
+    /// Every column has an entry.
+    pub cards_by_column: HashMap<String, Vec<Card>>,
+}
+
+/// Reads a live board with its visible columns and cards. Returns `None` if
+/// the board doesn't exist or is in the trash. Run it inside a transaction so
+/// all the reads see the same state.
+pub(crate) fn board_snapshot(conn: &Connection, board_id: &str) -> rusqlite::Result<Option<BoardSnapshot>> {
+    let Some(board) = fetch_live_board(conn, board_id)? else {
+        return Ok(None);
+    };
+
+    let columns = columns_for_board(conn, board_id)?;
+    let lanes = lanes_for_board(conn, board_id)?;
+    let mut cards_by_column: HashMap<String, Vec<Card>> =
+        columns.iter().map(|c| (c.id.clone(), Vec::new())).collect();
+
+    // Cards in archived columns are left out along with their column
+    for card in cards_for_board(conn, board_id)? {
+        if let Some(cards) = cards_by_column.get_mut(&card.column_id) {
+            cards.push(card);
+        }
+    }
+
+    Ok(Some(BoardSnapshot { board, columns, lanes, cards_by_column }))
+}
+
+/// Starting columns for a new board.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum BoardPreset {
+    /// To Do, Doing and Done.
+    Basic,
+    /// Backlog, To Do, In Progress, Review and Done.
+    Scrum,
+    /// No columns at all.
+    #[default]
+    None,
+}
+
+impl BoardPreset {
+    fn column_names(self) -> &'static [&'static str] {
+        match self {
+            BoardPreset::Basic => &["To Do", "Doing", "Done"],
+            BoardPreset::Scrum => &["Backlog", "To Do", "In Progress", "Review", "Done"],
+            BoardPreset::None => &[],
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateBoardInput {
     pub name: String,
+    /// Leaving it out creates the board without columns; the app asks for
+    /// `Basic` so the default columns come from here alone.
+    #[serde(default)]
+    pub preset: Option<BoardPreset>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -28,60 +191,57 @@ pub struct UpdateBoardInput {
 }
 
 #[tauri::command]
-pub fn get_all_boards(db: tauri::State<'_, Arc<Database>>) -> Result<Vec<Board>, String> {
+pub fn get_all_boards(db: tauri::State<'_, Arc<Database>>) -> Result<Vec<Board>, AppError> {
     db.with_connection(|conn| {
-        let mut stmt = conn.prepare(
-            r#"SELECT id, name, last_opened_at, created_at, updated_at
+        let mut stmt = conn.prepare(&format!(
+            r#"SELECT {BOARD_COLUMNS}
                FROM boards
-               ORDER BY last_opened_at DESC NULLS LAST, created_at DESC"#,
-        )?;
+               WHERE deleted_at IS NULL
+               ORDER BY last_opened_at DESC NULLS LAST, created_at DESC"#
+        ))?;
 
         let boards = stmt
-            .query_map([], |row| {
-                Ok(Board {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    last_opened_at: row.get(2)?,
-                    created_at: row.get(3)?,
-                    updated_at: row.get(4)?,
-                })
-            })?
+            .query_map([], Board::from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(boards)
     })
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn get_board(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<Option<Board>, String> {
+pub fn get_board(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<Option<Board>, AppError> {
     db.with_connection(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, name, last_opened_at, created_at, updated_at FROM boards WHERE id = ?",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {BOARD_COLUMNS} FROM boards WHERE id = ? AND deleted_at IS NULL"
+        ))?;
 
         let board = stmt
-            .query_row([&id], |row| {
-                Ok(Board {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    last_opened_at: row.get(2)?,
-                    created_at: row.get(3)?,
-                    updated_at: row.get(4)?,
-                })
-            })
+            .query_row([&id], Board::from_row)
             .optional()?;
 
         Ok(board)
     })
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
+}
+
+/// Load a board with its columns and cards in a single call
+#[tauri::command]
+pub fn load_board(
+    db: tauri::State<'_, Arc<Database>>,
+    board_id: String,
+) -> Result<Option<BoardSnapshot>, AppError> {
+    db.with_transaction(|tx| Ok(board_snapshot(tx, &board_id)?))
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub fn create_board(
     db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
     input: CreateBoardInput,
-) -> Result<Board, String> {
+) -> Result<Board, AppError> {
+    input.validate(&limits)?;
     let id = Uuid::new_v4().to_string();
     let now = Utc::now().to_rfc3339();
 
@@ -90,23 +250,28 @@ pub fn create_board(
         name: input.name,
         last_opened_at: Some(now.clone()),
         created_at: now.clone(),
-        updated_at: now,
+        updated_at: now.clone(),
     };
 
-    db.with_connection(|conn| {
-        conn.execute(
-            "INSERT INTO boards (id, name, last_opened_at, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
-            rusqlite::params![
-                &board.id,
-                &board.name,
-                &board.last_opened_at,
-                &board.created_at,
-                &board.updated_at
-            ],
+    db.with_transaction(|tx| {
+        insert_board(tx, &board)?;
+        let columns = insert_preset_columns(tx, &board.id, input.preset.unwrap_or_default(), &now)?;
+
+        // Trashing the board hides its columns with it
+        let forward: Vec<Op> = std::iter::once(Op::PutBoard { board: board.clone() })
+            .chain(columns.into_iter().map(|column| Op::PutColumn { column }))
+            .chain(std::iter::once(Op::untrash(TrashKind::Board, &board.id)))
+            .collect();
+        history::record(
+            tx,
+            &board.id,
+            "create_board",
+            &format!("Create board \"{}\"", board.name),
+            &forward,
+            &[Op::trash(TrashKind::Board, &board.id)],
         )?;
         Ok(())
-    })
-    .map_err(|e| e.to_string())?;
+    })?;
 
     Ok(board)
 }
@@ -114,47 +279,61 @@ pub fn create_board(
 #[tauri::command]
 pub fn update_board(
     db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
     id: String,
     input: UpdateBoardInput,
-) -> Result<Board, String> {
+) -> Result<Board, AppError> {
+    input.validate(&limits)?;
     let now = Utc::now().to_rfc3339();
 
-    db.with_connection(|conn| {
+    db.with_transaction(|tx| {
+        let before = fetch_board(tx, &id)?
+            .ok_or_else(|| DbError::NotFound { kind: "board", id: id.clone() })?;
+
         if let Some(name) = &input.name {
-            conn.execute(
+            tx.execute(
                 "UPDATE boards SET name = ?, updated_at = ? WHERE id = ?",
                 rusqlite::params![name, &now, &id],
             )?;
         }
 
-        let mut stmt = conn.prepare(
-            "SELECT id, name, last_opened_at, created_at, updated_at FROM boards WHERE id = ?",
+        let board = tx.query_row(
+            &format!("SELECT {BOARD_COLUMNS} FROM boards WHERE id = ?"),
+            [&id],
+            Board::from_row,
         )?;
 
-        stmt.query_row([&id], |row| {
-            Ok(Board {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                last_opened_at: row.get(2)?,
-                created_at: row.get(3)?,
-                updated_at: row.get(4)?,
-            })
-        })
+        if input.name.is_some() {
+            history::record(
+                tx,
+                &id,
+                "update_board",
+                &format!("Rename board \"{}\" to \"{}\"", before.name, board.name),
+                &[Op::PutBoard { board: board.clone() }],
+                &[Op::PutBoard { board: before }],
+            )?;
+        }
+
+        Ok(board)
     })
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn delete_board(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), String> {
-    db.with_connection(|conn| {
-        conn.execute("DELETE FROM boards WHERE id = ?", [&id])?;
+pub fn delete_board(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), AppError> {
+    db.with_transaction(|tx| {
+        let Some(board) = fetch_board(tx, &id)? else {
+            return Ok(());
+        };
+
+        trash::move_to_trash(tx, TrashKind::Board, &id, &id, &format!("Delete board \"{}\"", board.name))?;
         Ok(())
     })
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn set_last_opened_board(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), String> {
+pub fn set_last_opened_board(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), AppError> {
     let now = Utc::now().to_rfc3339();
 
     db.with_connection(|conn| {
@@ -164,13 +343,13 @@ pub fn set_last_opened_board(db: tauri::State<'_, Arc<Database>>, id: String) ->
         )?;
         Ok(())
     })
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::test_helpers::test_helpers::create_test_db;
+    use crate::db::test_helpers::test_helpers::{column, create_test_db, seed_board, seed_card, seed_column};
 
     #[test]
     fn test_create_board() {
@@ -202,6 +381,32 @@ mod tests {
         assert!(!board.id.is_empty());
     }
 
+    #[test]
+    fn test_insert_preset_columns() {
+        let (db, _temp) = create_test_db();
+
+        let board_id = db.with_connection(|conn| Ok(seed_board(conn, "Board")?.id)).unwrap();
+
+        let now = Utc::now().to_rfc3339();
+        db.with_connection(|conn| insert_preset_columns(conn, &board_id, BoardPreset::Basic, &now)).unwrap();
+        let names: Vec<_> = db
+            .with_connection(|conn| columns_for_board(conn, &board_id))
+            .unwrap()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, vec!["To Do", "Doing", "Done"]);
+
+        let scrum = db.with_connection(|conn| insert_preset_columns(conn, &board_id, BoardPreset::Scrum, &now)).unwrap();
+        assert_eq!(scrum.len(), 5);
+        assert_eq!(scrum[0].name, "Backlog");
+        assert!(db.with_connection(|conn| insert_preset_columns(conn, &board_id, BoardPreset::None, &now)).unwrap().is_empty());
+        assert_eq!(BoardPreset::default(), BoardPreset::None);
+
+        let preset: BoardPreset = serde_json::from_str(r#""scrum""#).unwrap();
+        assert_eq!(preset, BoardPreset::Scrum);
+    }
+
     #[test]
     fn test_get_all_boards() {
         let (db, _temp) = create_test_db();
@@ -274,7 +479,7 @@ mod tests {
         let (db, _temp) = create_test_db();
 
         // Create board, column, and card
-        let (board_id, col_id) = db.with_connection(|conn| {
+        let (board_id, _col_id) = db.with_connection(|conn| {
             let board_id = Uuid::new_v4().to_string();
             let col_id = Uuid::new_v4().to_string();
             let card_id = Uuid::new_v4().to_string();
@@ -373,4 +578,36 @@ mod tests {
 
         assert!(updated_board.last_opened_at.is_some());
     }
+
+    #[test]
+    fn test_load_board_snapshot() {
+        let (db, _temp) = create_test_db();
+
+        let (board_id, todo_id, done_id) = db.with_connection(|conn| {
+            let board = seed_board(conn, "Board")?;
+            let done = seed_column(conn, &board.id, "Done", 2.0)?;
+            let todo = seed_column(conn, &board.id, "To Do", 1.0)?;
+            let old = Column { archived: true, ..column(&board.id, "Old", 3.0) };
+            insert_column(conn, &old)?;
+
+            for (column_id, title, order) in [(&todo.id, "Second", 2.0), (&todo.id, "First", 1.0), (&old.id, "Hidden", 1.0)] {
+                seed_card(conn, column_id, title, order)?;
+            }
+
+            Ok((board.id, todo.id, done.id))
+        }).unwrap();
+
+        let snapshot = db.with_connection(|conn| board_snapshot(conn, &board_id)).unwrap().unwrap();
+
+        assert_eq!(snapshot.board.id, board_id);
+        let names: Vec<_> = snapshot.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["To Do", "Done"]);
+        assert_eq!(snapshot.cards_by_column.len(), 2);
+
+        let titles: Vec<_> = snapshot.cards_by_column[&todo_id].iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["First", "Second"]);
+        assert!(snapshot.cards_by_column[&done_id].is_empty());
+
+        assert!(db.with_connection(|conn| board_snapshot(conn, "missing")).unwrap().is_none());
+    }
 }
@@ -1,6 +1,16 @@
-use crate::db::Database;
-use chrono::Utc;
-use serde::{Deserialize, Serialize};
+use crate::commands::activity;
+use crate::commands::checklists::ChecklistSummary;
+use crate::commands::columns::{board_id_for_column, check_wip_limit, fetch_column, live_column_board, WipExceeded};
+use crate::commands::history::{self, Op};
+use crate::commands::lanes::fetch_lane;
+use crate::commands::links::{open_blockers, LinkedCard};
+use crate::commands::trash::{self, TrashKind};
+use crate::db::{Database, DbError};
+use crate::error::AppError;
+use crate::validation::{Limits, Validate};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -15,6 +25,242 @@ pub struct Card {
     pub archived: bool,
     pub created_at: String,
     pub updated_at: String,
+    #[serde(default)]
+    pub label_ids: Vec<String>,
+    #[serde(default)]
+    pub due_at: Option<String>,
+    #[serde(default)]
+    pub start_at: Option<String>,
+    #[serde(default)]
+    pub checklist: ChecklistSummary,
+    /// The swimlane the card sits in, if any.
+    #[serde(default)]
+    pub lane_id: Option<String>,
+    #[serde(default)]
+    pub priority: Option<Priority>,
+    /// Size in story points.
+    #[serde(default)]
+    pub estimate: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl Priority {
+    fn as_str(self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+            Priority::Urgent => "urgent",
+        }
+    }
+
+    fn parse(priority: &str) -> Option<Self> {
+        match priority {
+            "low" => Some(Priority::Low),
+            "medium" => Some(Priority::Medium),
+            "high" => Some(Priority::High),
+            "urgent" => Some(Priority::Urgent),
+            _ => None,
+        }
+    }
+}
+
+impl rusqlite::ToSql for Priority {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        Ok(self.as_str().into())
+    }
+}
+
+/// Column list shared by every card query so rows map through `Card::from_row`.
+/// Must be selected `FROM cards` without an alias for the subqueries to bind.
+pub(crate) const CARD_COLUMNS: &str = r#"id, column_id, title, description, "order", archived, created_at, updated_at,
+    (SELECT GROUP_CONCAT(label_id) FROM card_labels WHERE card_id = cards.id), due_at, start_at,
+    (SELECT COUNT(*) FROM checklist_items WHERE card_id = cards.id AND done = 1),
+    (SELECT COUNT(*) FROM checklist_items WHERE card_id = cards.id), lane_id, priority, estimate"#;
+
+impl Card {
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let label_ids: Option<String> = row.get(8)?;
+        let priority: Option<String> = row.get(14)?;
+
+        Ok(Card {
+            id: row.get(0)?,
+            column_id: row.get(1)?,
+            title: row.get(2)?,
+            description: row.get(3)?,
+            order: row.get(4)?,
+            archived: row.get::<_, i32>(5)? != 0,
+            created_at: row.get(6)?,
+            updated_at: row.get(7)?,
+            label_ids: label_ids
+                .map(|ids| ids.split(',').map(str::to_string).collect())
+                .unwrap_or_default(),
+            due_at: row.get(9)?,
+            start_at: row.get(10)?,
+            checklist: ChecklistSummary {
+                done: row.get(11)?,
+                total: row.get(12)?,
+            },
+            lane_id: row.get(13)?,
+            priority: priority
+                .map(|p| {
+                    Priority::parse(&p).ok_or_else(|| {
+                        rusqlite::Error::FromSqlConversionFailure(14, rusqlite::types::Type::Text, p.into())
+                    })
+                })
+                .transpose()?,
+            estimate: row.get(15)?,
+        })
+    }
+}
+
+pub(crate) fn fetch_card(conn: &Connection, id: &str) -> rusqlite::Result<Option<Card>> {
+    conn.query_row(
+        &format!("SELECT {CARD_COLUMNS} FROM cards WHERE id = ?"),
+        [id],
+        Card::from_row,
+    )
+    .optional()
+}
+
+/// Like `fetch_card`, but skips cards that are archived or in the trash, the
+/// same way `live_column_board` does for columns.
+pub(crate) fn fetch_live_card(conn: &Connection, id: &str) -> rusqlite::Result<Option<Card>> {
+    conn.query_row(
+        &format!("SELECT {CARD_COLUMNS} FROM cards WHERE id = ? AND archived = 0 AND deleted_at IS NULL"),
+        [id],
+        Card::from_row,
+    )
+    .optional()
+}
+
+/// Writes a card row along with its label assignments.
+pub(crate) fn insert_card(conn: &Connection, card: &Card) -> rusqlite::Result<()> {
+    write_card(conn, card, "")
+}
+
+/// Inserts the card or overwrites the existing row in place. Updating in place
+/// (rather than `INSERT OR REPLACE`) keeps rows that cascade from the card.
+/// Label assignments are only ever added, never removed.
+pub(crate) fn upsert_card(conn: &Connection, card: &Card) -> rusqlite::Result<()> {
+    write_card(
+        conn,
+        card,
+        r#"ON CONFLICT(id) DO UPDATE SET column_id = excluded.column_id, title = excluded.title,
+               description = excluded.description, "order" = excluded."order", archived = excluded.archived,
+               created_at = excluded.created_at, updated_at = excluded.updated_at,
+               due_at = excluded.due_at, start_at = excluded.start_at, lane_id = excluded.lane_id,
+               priority = excluded.priority, estimate = excluded.estimate"#,
+    )
+}
+
+fn write_card(conn: &Connection, card: &Card, on_conflict: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            r#"INSERT INTO cards (id, column_id, title, description, "order", archived, created_at, updated_at, due_at, start_at,
+                                 lane_id, priority, estimate)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, (SELECT id FROM lanes WHERE id = ?), ?, ?) {on_conflict}"#
+        ),
+        rusqlite::params![
+            &card.id,
+            &card.column_id,
+            &card.title,
+            &card.description,
+            &card.order,
+            card.archived as i32,
+            &card.created_at,
+            &card.updated_at,
+            &card.due_at,
+            &card.start_at,
+            &card.lane_id,
+            &card.priority,
+            &card.estimate
+        ],
+    )?;
+
+    // Labels and lanes deleted since a snapshot was taken are skipped
+    for label_id in &card.label_ids {
+        conn.execute(
+            "INSERT OR IGNORE INTO card_labels (card_id, label_id) SELECT ?, id FROM labels WHERE id = ?",
+            [&card.id, label_id],
+        )?;
+    }
+    Ok(())
+}
+
+/// Normalizes an RFC 3339 timestamp to UTC with second precision so stored
+/// dates compare correctly as text regardless of the offset the client sent.
+pub(crate) fn normalize_timestamp(value: &str) -> Result<String, AppError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc).to_rfc3339_opts(SecondsFormat::Secs, true))
+        .map_err(|e| AppError::Validation(format!("Invalid timestamp '{}': {}", value, e)))
+}
+
+/// Live (not archived or trashed) cards on a board, in display order: by
+/// lane, then column, then position. Cards without a lane come last.
+pub(crate) fn cards_for_board(conn: &Connection, board_id: &str) -> rusqlite::Result<Vec<Card>> {
+    let mut stmt = conn.prepare(&format!(
+        r#"SELECT {CARD_COLUMNS}
+           FROM cards
+           WHERE column_id IN (SELECT id FROM columns WHERE board_id = ? AND deleted_at IS NULL)
+             AND archived = 0 AND deleted_at IS NULL
+           ORDER BY (SELECT lanes."order" FROM lanes WHERE lanes.id = cards.lane_id) ASC NULLS LAST,
+                    (SELECT columns."order" FROM columns WHERE columns.id = cards.column_id) ASC,
+                    "order" ASC"#
+    ))?;
+
+    let cards = stmt
+        .query_map([board_id], Card::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(cards)
+}
+
+/// Cards on a board whose due date is strictly before `now`.
+pub(crate) fn overdue_cards(conn: &Connection, board_id: &str, now: &str) -> rusqlite::Result<Vec<Card>> {
+    let mut stmt = conn.prepare(&format!(
+        r#"SELECT {CARD_COLUMNS}
+           FROM cards
+           WHERE column_id IN (SELECT id FROM columns WHERE board_id = ? AND deleted_at IS NULL)
+             AND archived = 0 AND deleted_at IS NULL AND due_at IS NOT NULL AND due_at < ?
+           ORDER BY due_at ASC"#
+    ))?;
+
+    let cards = stmt
+        .query_map([board_id, now], Card::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(cards)
+}
+
+/// Cards on a board due within the inclusive range `[from, to]`.
+pub(crate) fn cards_due_between(
+    conn: &Connection,
+    board_id: &str,
+    from: &str,
+    to: &str,
+) -> rusqlite::Result<Vec<Card>> {
+    let mut stmt = conn.prepare(&format!(
+        r#"SELECT {CARD_COLUMNS}
+           FROM cards
+           WHERE column_id IN (SELECT id FROM columns WHERE board_id = ? AND deleted_at IS NULL)
+             AND archived = 0 AND deleted_at IS NULL AND due_at BETWEEN ? AND ?
+           ORDER BY due_at ASC, "order" ASC"#
+    ))?;
+
+    let cards = stmt
+        .query_map([board_id, from, to], Card::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(cards)
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,6 +270,24 @@ pub struct CreateCardInput {
     pub title: String,
     pub description: Option<String>,
     pub order: Option<f64>,
+    pub due_at: Option<String>,
+    pub start_at: Option<String>,
+    /// Must be a lane on the column's board.
+    #[serde(default)]
+    pub lane_id: Option<String>,
+    #[serde(default)]
+    pub priority: Option<Priority>,
+    #[serde(default)]
+    pub estimate: Option<f64>,
+}
+
+/// Tells a field sent as `null` (clear it) apart from one left out (keep it).
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 #[derive(Debug, Deserialize)]
@@ -33,6 +297,18 @@ pub struct UpdateCardInput {
     pub description: Option<String>,
     pub order: Option<f64>,
     pub archived: Option<bool>,
+    /// `null` clears the due date.
+    #[serde(default, deserialize_with = "nullable")]
+    pub due_at: Option<Option<String>>,
+    /// `null` clears the start date.
+    #[serde(default, deserialize_with = "nullable")]
+    pub start_at: Option<Option<String>>,
+    /// `null` clears the priority.
+    #[serde(default, deserialize_with = "nullable")]
+    pub priority: Option<Option<Priority>>,
+    /// `null` clears the estimate.
+    #[serde(default, deserialize_with = "nullable")]
+    pub estimate: Option<Option<f64>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -42,6 +318,15 @@ pub struct MoveCardInput {
     pub order: f64,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveCardToLaneInput {
+    pub column_id: String,
+    /// `None` takes the card out of every lane.
+    pub lane_id: Option<String>,
+    pub order: f64,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BatchUpdateOrderInput {
@@ -49,125 +334,526 @@ pub struct BatchUpdateOrderInput {
     pub order: f64,
 }
 
+/// Applies every order update or none of them. The error names the first
+/// card that was missing, failed to update, or sits on another board than
+/// the first card, since the batch is undone as one step of that board.
+pub(crate) fn update_orders(
+    conn: &Connection,
+    updates: &[BatchUpdateOrderInput],
+    now: &str,
+) -> Result<(), DbError> {
+    let mut board_id: Option<String> = None;
+    let mut before = Vec::new();
+    let mut after = Vec::new();
+
+    for update in updates {
+        let failed = |source| DbError::ItemFailed { kind: "card", id: update.id.clone(), source };
+
+        let card = fetch_card(conn, &update.id)
+            .map_err(failed)?
+            .ok_or_else(|| DbError::NotFound { kind: "card", id: update.id.clone() })?;
+        let card_board = board_id_for_column(conn, &card.column_id).map_err(failed)?;
+        if *board_id.get_or_insert_with(|| card_board.clone()) != card_board {
+            return Err(DbError::WrongBoard { kind: "card", id: update.id.clone() });
+        }
+        conn.execute(
+            r#"UPDATE cards SET "order" = ?, updated_at = ? WHERE id = ?"#,
+            rusqlite::params![update.order, now, &update.id],
+        )
+        .map_err(failed)?;
+
+        after.push(Op::PutCard {
+            card: Card { order: update.order, updated_at: now.to_string(), ..card.clone() },
+        });
+        before.push(Op::PutCard { card });
+    }
+
+    if let Some(board_id) = board_id {
+        history::record(conn, &board_id, "reorder_cards", "Reorder cards", &after, &before)?;
+    }
+
+    Ok(())
+}
+
+/// An order strictly between two neighbours, or `None` when float precision
+/// can't fit another card between them.
+fn order_between(lo: Option<f64>, hi: Option<f64>) -> Option<f64> {
+    let order = match (lo, hi) {
+        (None, None) => 1.0,
+        (Some(lo), None) => lo + 1.0,
+        (None, Some(hi)) => hi - 1.0,
+        (Some(lo), Some(hi)) => lo + (hi - lo) / 2.0,
+    };
+    let fits = lo.is_none_or(|lo| lo < order) && hi.is_none_or(|hi| order < hi);
+    (order.is_finite() && fits).then_some(order)
+}
+
+/// Moves a card into `column_id` directly after `before_id` and/or directly
+/// before `after_id` (neither means the end of the column), computing its
+/// order server-side. If the neighbours are too close together, the column is
+/// renumbered as part of the same write. Warns like `move_to` when the card
+/// changes column. Returns `None` when the card is gone, archived or trashed,
+/// or the neighbours don't match the current state, e.g. because the client is
+/// stale.
+pub(crate) fn move_between(
+    conn: &Connection,
+    card_id: &str,
+    column_id: &str,
+    before_id: Option<&str>,
+    after_id: Option<&str>,
+    now: &str,
+) -> Result<Option<CardWithWarnings>, DbError> {
+    let Some(card) = fetch_live_card(conn, card_id)? else {
+        return Ok(None);
+    };
+    let board_id = target_board(conn, &card, column_id)?;
+    let from_column = card.column_id.clone();
+
+    let siblings = conn
+        .prepare(&format!(
+            r#"SELECT {CARD_COLUMNS}
+               FROM cards
+               WHERE column_id = ? AND id != ? AND archived = 0 AND deleted_at IS NULL
+               ORDER BY "order" ASC, id ASC"#
+        ))?
+        .query_map([column_id, card_id], Card::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let position = |id: &str| siblings.iter().position(|c| c.id == id);
+    let index = match (before_id, after_id) {
+        (Some(before_id), _) => position(before_id).map(|i| i + 1),
+        (None, Some(after_id)) => position(after_id),
+        (None, None) => Some(siblings.len()),
+    };
+    let Some(index) = index else {
+        return Ok(None);
+    };
+    if after_id.is_some() && siblings.get(index).map(|c| c.id.as_str()) != after_id {
+        return Ok(None);
+    }
+
+    let mut forward = Vec::new();
+    let mut inverse = Vec::new();
+
+    let lo = index.checked_sub(1).map(|i| siblings[i].order);
+    let hi = siblings.get(index).map(|c| c.order);
+    let order = match order_between(lo, hi) {
+        Some(order) => order,
+        None => {
+            // Out of precision: space the column out again, leaving a slot at `index`
+            for (i, sibling) in siblings.iter().enumerate() {
+                let renumbered = if i < index { i + 1 } else { i + 2 } as f64;
+                conn.execute(
+                    r#"UPDATE cards SET "order" = ?, updated_at = ? WHERE id = ?"#,
+                    rusqlite::params![renumbered, now, &sibling.id],
+                )?;
+                forward.push(Op::PutCard {
+                    card: Card { order: renumbered, updated_at: now.to_string(), ..sibling.clone() },
+                });
+                inverse.push(Op::PutCard { card: sibling.clone() });
+            }
+            (index + 1) as f64
+        }
+    };
+
+    conn.execute(
+        r#"UPDATE cards SET column_id = ?, "order" = ?, updated_at = ? WHERE id = ?"#,
+        rusqlite::params![column_id, order, now, card_id],
+    )?;
+    let moved = Card {
+        column_id: column_id.to_string(),
+        order,
+        updated_at: now.to_string(),
+        ..card.clone()
+    };
+
+    activity::record_changes(conn, &card, &moved, now)?;
+    forward.push(Op::PutCard { card: moved.clone() });
+    inverse.push(Op::PutCard { card });
+    history::record(
+        conn,
+        &board_id,
+        "move_card",
+        &format!("Move card \"{}\"", moved.title),
+        &forward,
+        &inverse,
+    )?;
+
+    // Reordering within a column never trips a limit
+    let warnings = if moved.column_id != from_column {
+        entry_warnings(conn, card_id, column_id)?
+    } else {
+        Vec::new()
+    };
+    Ok(Some(CardWithWarnings { card: moved, warnings }))
+}
+
+/// Something worth telling the user about a write that still went through.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum CardWarning {
+    WipLimitExceeded(WipExceeded),
+    /// The card went into a done column while cards blocking it are unfinished.
+    OpenBlockers { blockers: Vec<LinkedCard> },
+}
+
+/// A card returned from a create or move, with any warnings it raised.
+/// Serializes as the card itself plus a `warnings` list when there are any.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardWithWarnings {
+    #[serde(flatten)]
+    pub card: Card,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<CardWarning>,
+}
+
+/// Puts a card at `order` in `column_id`. `lane` of `None` leaves the card's
+/// lane alone, `Some(None)` takes it out of its lane. Archived and trashed
+/// cards count as not found.
+pub(crate) fn move_to(
+    conn: &Connection,
+    id: &str,
+    column_id: &str,
+    lane: Option<Option<&str>>,
+    order: f64,
+    now: &str,
+) -> Result<CardWithWarnings, DbError> {
+    let before = fetch_live_card(conn, id)?
+        .ok_or_else(|| DbError::NotFound { kind: "card", id: id.to_string() })?;
+    let board_id = target_board(conn, &before, column_id)?;
+    if let Some(Some(lane_id)) = lane {
+        check_lane_board(conn, lane_id, &board_id)?;
+    }
+
+    conn.execute(
+        r#"UPDATE cards SET column_id = ?, "order" = ?, updated_at = ? WHERE id = ?"#,
+        rusqlite::params![column_id, order, now, id],
+    )?;
+    if let Some(lane_id) = lane {
+        conn.execute("UPDATE cards SET lane_id = ? WHERE id = ?", rusqlite::params![lane_id, id])?;
+    }
+
+    // Fetch updated card
+    let card = conn.query_row(
+        &format!("SELECT {CARD_COLUMNS} FROM cards WHERE id = ?"),
+        [id],
+        Card::from_row,
+    )?;
+
+    // Reordering within a column never trips a limit
+    let mut warnings = Vec::new();
+    if before.column_id != card.column_id {
+        warnings = entry_warnings(conn, id, &card.column_id)?;
+    }
+
+    activity::record_changes(conn, &before, &card, now)?;
+    history::record(
+        conn,
+        &board_id,
+        "move_card",
+        &format!("Move card \"{}\"", card.title),
+        &[Op::PutCard { card: card.clone() }],
+        &[Op::PutCard { card: before }],
+    )?;
+
+    Ok(CardWithWarnings { card, warnings })
+}
+
+/// The board `column_id` is on. Fails unless the column is live and on the
+/// same board as `card`.
+fn target_board(conn: &Connection, card: &Card, column_id: &str) -> Result<String, DbError> {
+    let board_id = live_column_board(conn, column_id)?
+        .ok_or_else(|| DbError::NotFound { kind: "column", id: column_id.to_string() })?;
+    if board_id != board_id_for_column(conn, &card.column_id)? {
+        return Err(DbError::WrongBoard { kind: "column", id: column_id.to_string() });
+    }
+    Ok(board_id)
+}
+
+/// Fails unless the lane exists and is on `board_id`.
+fn check_lane_board(conn: &Connection, lane_id: &str, board_id: &str) -> Result<(), DbError> {
+    let lane = fetch_lane(conn, lane_id)?
+        .ok_or_else(|| DbError::NotFound { kind: "lane", id: lane_id.to_string() })?;
+    if lane.board_id != board_id {
+        return Err(DbError::WrongBoard { kind: "lane", id: lane_id.to_string() });
+    }
+    Ok(())
+}
+
+/// Checks what a card entering `column_id` runs into. Fails instead when a
+/// rule is set to block, see `check_wip_limit`.
+pub(crate) fn entry_warnings(conn: &Connection, card_id: &str, column_id: &str) -> Result<Vec<CardWarning>, DbError> {
+    let mut warnings = Vec::new();
+    if let Some(exceeded) = check_wip_limit(conn, column_id)? {
+        warnings.push(CardWarning::WipLimitExceeded(exceeded));
+    }
+    if fetch_column(conn, column_id)?.is_some_and(|column| column.done) {
+        let blockers = open_blockers(conn, card_id)?;
+        if !blockers.is_empty() {
+            warnings.push(CardWarning::OpenBlockers { blockers });
+        }
+    }
+    Ok(warnings)
+}
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInput {
+    pub offset: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardPage {
+    pub cards: Vec<Card>,
+    /// Number of matching cards across all pages.
+    pub total: i64,
+}
+
+/// Archived cards on a board, most recently changed first. Cards in trashed
+/// columns are left out.
+pub(crate) fn archived_cards(
+    conn: &Connection,
+    board_id: &str,
+    page: &PageInput,
+) -> rusqlite::Result<CardPage> {
+    let offset = page.offset.unwrap_or(0).max(0);
+    let limit = page.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let filter = r#"FROM cards
+           WHERE column_id IN (SELECT id FROM columns WHERE board_id = ?1 AND deleted_at IS NULL)
+             AND archived = 1 AND deleted_at IS NULL"#;
+
+    let total = conn.query_row(&format!("SELECT COUNT(*) {filter}"), [board_id], |row| row.get(0))?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {CARD_COLUMNS} {filter} ORDER BY updated_at DESC, id LIMIT ?2 OFFSET ?3"
+    ))?;
+    let cards = stmt
+        .query_map(rusqlite::params![board_id, limit, offset], Card::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(CardPage { cards, total })
+}
+
+/// Clears the archived flag on each card and journals it as one change.
+/// Returns the cards that were actually unarchived. Fails if that takes a
+/// column with a blocking WIP limit past it.
+pub(crate) fn unarchive(conn: &Connection, ids: &[String], now: &str) -> Result<Vec<Card>, DbError> {
+    let mut before = Vec::new();
+    let mut after = Vec::new();
+
+    for id in ids {
+        let Some(card) = fetch_card(conn, id)?.filter(|c| c.archived) else {
+            continue;
+        };
+        conn.execute(
+            "UPDATE cards SET archived = 0, updated_at = ? WHERE id = ?",
+            rusqlite::params![now, id],
+        )?;
+        let restored = Card { archived: false, updated_at: now.to_string(), ..card.clone() };
+        activity::record_changes(conn, &card, &restored, now)?;
+        after.push(restored);
+        before.push(card);
+    }
+
+    if let Some(card) = before.first() {
+        let board_id = board_id_for_column(conn, &card.column_id)?;
+        history::record(
+            conn,
+            &board_id,
+            "unarchive_cards",
+            &format!("Unarchive {} card(s)", after.len()),
+            &after.iter().cloned().map(|card| Op::PutCard { card }).collect::<Vec<_>>(),
+            &before.into_iter().map(|card| Op::PutCard { card }).collect::<Vec<_>>(),
+        )?;
+    }
+
+    let mut columns: Vec<&str> = after.iter().map(|card| card.column_id.as_str()).collect();
+    columns.sort_unstable();
+    columns.dedup();
+    for column_id in columns {
+        check_wip_limit(conn, column_id)?;
+    }
+
+    Ok(after)
+}
+
 #[tauri::command]
 pub fn get_cards_for_board(
     db: tauri::State<'_, Arc<Database>>,
     board_id: String,
-) -> Result<Vec<Card>, String> {
-    db.with_connection(|conn| {
-        let mut stmt = conn.prepare(
-            r#"SELECT c.id, c.column_id, c.title, c.description, c."order", c.archived, c.created_at, c.updated_at
-               FROM cards c
-               INNER JOIN columns col ON c.column_id = col.id
-               WHERE col.board_id = ? AND c.archived = 0
-               ORDER BY c."order" ASC"#,
-        )?;
-
-        let cards = stmt
-            .query_map([&board_id], |row| {
-                Ok(Card {
-                    id: row.get(0)?,
-                    column_id: row.get(1)?,
-                    title: row.get(2)?,
-                    description: row.get(3)?,
-                    order: row.get(4)?,
-                    archived: row.get::<_, i32>(5)? != 0,
-                    created_at: row.get(6)?,
-                    updated_at: row.get(7)?,
-                })
-            })?
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(cards)
-    })
-    .map_err(|e| e.to_string())
+) -> Result<Vec<Card>, AppError> {
+    db.with_connection(|conn| cards_for_board(conn, &board_id))
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub fn get_cards_for_column(
     db: tauri::State<'_, Arc<Database>>,
     column_id: String,
-) -> Result<Vec<Card>, String> {
+) -> Result<Vec<Card>, AppError> {
     db.with_connection(|conn| {
-        let mut stmt = conn.prepare(
-            r#"SELECT id, column_id, title, description, "order", archived, created_at, updated_at
+        let mut stmt = conn.prepare(&format!(
+            r#"SELECT {CARD_COLUMNS}
                FROM cards
-               WHERE column_id = ? AND archived = 0
-               ORDER BY "order" ASC"#,
-        )?;
+               WHERE column_id = ? AND archived = 0 AND deleted_at IS NULL
+               ORDER BY "order" ASC"#
+        ))?;
 
         let cards = stmt
-            .query_map([&column_id], |row| {
-                Ok(Card {
-                    id: row.get(0)?,
-                    column_id: row.get(1)?,
-                    title: row.get(2)?,
-                    description: row.get(3)?,
-                    order: row.get(4)?,
-                    archived: row.get::<_, i32>(5)? != 0,
-                    created_at: row.get(6)?,
-                    updated_at: row.get(7)?,
-                })
-            })?
+            .query_map([&column_id], Card::from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(cards)
     })
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub fn create_card(
     db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
     input: CreateCardInput,
-) -> Result<Card, String> {
+) -> Result<CardWithWarnings, AppError> {
+    input.validate(&limits)?;
     let id = Uuid::new_v4().to_string();
     let now = Utc::now().to_rfc3339();
+    let due_at = input.due_at.as_deref().map(normalize_timestamp).transpose()?;
+    let start_at = input.start_at.as_deref().map(normalize_timestamp).transpose()?;
+
+    db.with_transaction(|tx| {
+        // The card goes on whichever board its column is on
+        let board_id = live_column_board(tx, &input.column_id)?
+            .ok_or_else(|| DbError::NotFound { kind: "column", id: input.column_id.clone() })?;
+        if let Some(lane_id) = &input.lane_id {
+            check_lane_board(tx, lane_id, &board_id)?;
+        }
 
-    // Get the max order for this column if order not provided
-    let order = if let Some(o) = input.order {
-        o
-    } else {
-        db.with_connection(|conn| {
-            let max_order: Option<f64> = conn.query_row(
+        // Get the max order for this column if order not provided
+        let order = if let Some(o) = input.order {
+            o
+        } else {
+            let max_order: Option<f64> = tx.query_row(
                 r#"SELECT MAX("order") FROM cards WHERE column_id = ?"#,
                 [&input.column_id],
                 |row| row.get(0),
             )?;
-            Ok(max_order.unwrap_or(0.0) + 1.0)
-        })
-        .map_err(|e| e.to_string())?
-    };
-
-    let card = Card {
-        id: id.clone(),
-        column_id: input.column_id,
-        title: input.title,
-        description: input.description,
-        order,
-        archived: false,
-        created_at: now.clone(),
-        updated_at: now,
-    };
-
-    db.with_connection(|conn| {
-        conn.execute(
-            r#"INSERT INTO cards (id, column_id, title, description, "order", archived, created_at, updated_at)
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
-            rusqlite::params![
-                &card.id,
-                &card.column_id,
-                &card.title,
-                &card.description,
-                &card.order,
-                card.archived as i32,
-                &card.created_at,
-                &card.updated_at
-            ],
+            max_order.unwrap_or(0.0) + 1.0
+        };
+
+        let card = Card {
+            id: id.clone(),
+            column_id: input.column_id.clone(),
+            title: input.title.clone(),
+            description: input.description.clone(),
+            order,
+            archived: false,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+            label_ids: Vec::new(),
+            due_at: due_at.clone(),
+            start_at: start_at.clone(),
+            checklist: ChecklistSummary::default(),
+            lane_id: input.lane_id.clone(),
+            priority: input.priority,
+            estimate: input.estimate,
+        };
+
+        insert_card(tx, &card)?;
+        let warnings = entry_warnings(tx, &card.id, &card.column_id)?;
+        activity::record_created(tx, &card, &now)?;
+        history::record(
+            tx,
+            &board_id,
+            "create_card",
+            &format!("Add card \"{}\"", card.title),
+            &[Op::PutCard { card: card.clone() }, Op::untrash(TrashKind::Card, &card.id)],
+            &[Op::trash(TrashKind::Card, &card.id)],
         )?;
-        Ok(())
+
+        Ok(CardWithWarnings { card, warnings })
     })
-    .map_err(|e| e.to_string())?;
+    .map_err(AppError::from)
+}
+
+/// Applies the fields set in `input`; dates must already be normalized.
+pub(crate) fn update(conn: &Connection, id: &str, input: &UpdateCardInput, now: &str) -> Result<Card, DbError> {
+    let before = fetch_card(conn, id)?
+        .ok_or_else(|| DbError::NotFound { kind: "card", id: id.to_string() })?;
+
+    // Build dynamic update query
+    let mut updates = vec!["updated_at = ?"];
+    let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(now.to_string())];
+
+    if let Some(title) = &input.title {
+        updates.push("title = ?");
+        params.push(Box::new(title.clone()));
+    }
+    if let Some(description) = &input.description {
+        updates.push("description = ?");
+        params.push(Box::new(description.clone()));
+    }
+    if let Some(order) = input.order {
+        updates.push(r#""order" = ?"#);
+        params.push(Box::new(order));
+    }
+    if let Some(archived) = input.archived {
+        updates.push("archived = ?");
+        params.push(Box::new(archived as i32));
+    }
+    if let Some(due_at) = &input.due_at {
+        updates.push("due_at = ?");
+        params.push(Box::new(due_at.clone()));
+    }
+    if let Some(start_at) = &input.start_at {
+        updates.push("start_at = ?");
+        params.push(Box::new(start_at.clone()));
+    }
+    if let Some(priority) = input.priority {
+        updates.push("priority = ?");
+        params.push(Box::new(priority));
+    }
+    if let Some(estimate) = input.estimate {
+        updates.push("estimate = ?");
+        params.push(Box::new(estimate));
+    }
+
+    params.push(Box::new(id.to_string()));
+
+    let query = format!(
+        "UPDATE cards SET {} WHERE id = ?",
+        updates.join(", ")
+    );
+
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+    conn.execute(&query, params_refs.as_slice())?;
+
+    // Fetch updated card
+    let card = conn.query_row(
+        &format!("SELECT {CARD_COLUMNS} FROM cards WHERE id = ?"),
+        [id],
+        Card::from_row,
+    )?;
+    // Unarchiving brings the card back into its column, as `unarchive` does
+    if before.archived && !card.archived {
+        check_wip_limit(conn, &card.column_id)?;
+    }
+
+    activity::record_changes(conn, &before, &card, now)?;
+    history::record(
+        conn,
+        &board_id_for_column(conn, &card.column_id)?,
+        "update_card",
+        &format!("Edit card \"{}\"", card.title),
+        &[Op::PutCard { card: card.clone() }],
+        &[Op::PutCard { card: before }],
+    )?;
 
     Ok(card)
 }
@@ -175,133 +861,159 @@ pub fn create_card(
 #[tauri::command]
 pub fn update_card(
     db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
     id: String,
-    input: UpdateCardInput,
-) -> Result<Card, String> {
+    mut input: UpdateCardInput,
+) -> Result<Card, AppError> {
+    input.validate(&limits)?;
     let now = Utc::now().to_rfc3339();
-
-    db.with_connection(|conn| {
-        // Build dynamic update query
-        let mut updates = vec!["updated_at = ?"];
-        let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(now.clone())];
-
-        if let Some(title) = &input.title {
-            updates.push("title = ?");
-            params.push(Box::new(title.clone()));
-        }
-        if let Some(description) = &input.description {
-            updates.push("description = ?");
-            params.push(Box::new(description.clone()));
+    // A date that is set must be a valid timestamp
+    let normalize_optional = |value: &Option<Option<String>>| -> Result<Option<Option<String>>, AppError> {
+        match value {
+            Some(Some(v)) => normalize_timestamp(v).map(|ts| Some(Some(ts))),
+            other => Ok(other.clone()),
         }
-        if let Some(order) = input.order {
-            updates.push(r#""order" = ?"#);
-            params.push(Box::new(order));
-        }
-        if let Some(archived) = input.archived {
-            updates.push("archived = ?");
-            params.push(Box::new(archived as i32));
-        }
-
-        params.push(Box::new(id.clone()));
-
-        let query = format!(
-            "UPDATE cards SET {} WHERE id = ?",
-            updates.join(", ")
-        );
-
-        let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
-        conn.execute(&query, params_refs.as_slice())?;
-
-        // Fetch updated card
-        let mut stmt = conn.prepare(
-            r#"SELECT id, column_id, title, description, "order", archived, created_at, updated_at
-               FROM cards WHERE id = ?"#,
-        )?;
+    };
+    input.due_at = normalize_optional(&input.due_at)?;
+    input.start_at = normalize_optional(&input.start_at)?;
 
-        stmt.query_row([&id], |row| {
-            Ok(Card {
-                id: row.get(0)?,
-                column_id: row.get(1)?,
-                title: row.get(2)?,
-                description: row.get(3)?,
-                order: row.get(4)?,
-                archived: row.get::<_, i32>(5)? != 0,
-                created_at: row.get(6)?,
-                updated_at: row.get(7)?,
-            })
-        })
-    })
-    .map_err(|e| e.to_string())
+    db.with_transaction(|tx| update(tx, &id, &input, &now))
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn delete_card(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), String> {
-    db.with_connection(|conn| {
-        conn.execute("DELETE FROM cards WHERE id = ?", [&id])?;
+pub fn delete_card(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), AppError> {
+    db.with_transaction(|tx| {
+        let Some(card) = fetch_card(tx, &id)? else {
+            return Ok(());
+        };
+
+        trash::move_to_trash(
+            tx,
+            TrashKind::Card,
+            &id,
+            &board_id_for_column(tx, &card.column_id)?,
+            &format!("Delete card \"{}\"", card.title),
+        )?;
         Ok(())
     })
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub fn move_card(
     db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
     id: String,
     input: MoveCardInput,
-) -> Result<Card, String> {
+) -> Result<CardWithWarnings, AppError> {
+    input.validate(&limits)?;
     let now = Utc::now().to_rfc3339();
 
-    db.with_connection(|conn| {
-        conn.execute(
-            r#"UPDATE cards SET column_id = ?, "order" = ?, updated_at = ? WHERE id = ?"#,
-            rusqlite::params![&input.column_id, input.order, &now, &id],
-        )?;
+    db.with_transaction(|tx| move_to(tx, &id, &input.column_id, None, input.order, &now))
+        .map_err(AppError::from)
+}
 
-        // Fetch updated card
-        let mut stmt = conn.prepare(
-            r#"SELECT id, column_id, title, description, "order", archived, created_at, updated_at
-               FROM cards WHERE id = ?"#,
-        )?;
+/// Move a card to another column and swimlane in one step
+#[tauri::command]
+pub fn move_card_to_lane(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    id: String,
+    input: MoveCardToLaneInput,
+) -> Result<CardWithWarnings, AppError> {
+    input.validate(&limits)?;
+    let now = Utc::now().to_rfc3339();
 
-        stmt.query_row([&id], |row| {
-            Ok(Card {
-                id: row.get(0)?,
-                column_id: row.get(1)?,
-                title: row.get(2)?,
-                description: row.get(3)?,
-                order: row.get(4)?,
-                archived: row.get::<_, i32>(5)? != 0,
-                created_at: row.get(6)?,
-                updated_at: row.get(7)?,
-            })
-        })
-    })
-    .map_err(|e| e.to_string())
+    db.with_transaction(|tx| move_to(tx, &id, &input.column_id, Some(input.lane_id.as_deref()), input.order, &now))
+        .map_err(AppError::from)
+}
+
+/// Move a card next to other cards, letting the backend pick its order
+#[tauri::command]
+pub fn move_card_between(
+    db: tauri::State<'_, Arc<Database>>,
+    card_id: String,
+    column_id: String,
+    before_id: Option<String>,
+    after_id: Option<String>,
+) -> Result<CardWithWarnings, AppError> {
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| move_between(tx, &card_id, &column_id, before_id.as_deref(), after_id.as_deref(), &now))?
+    .ok_or_else(|| AppError::Conflict("The card or its neighbours have changed; reload the board and try again".to_string()))
+}
+
+#[tauri::command]
+pub fn get_overdue_cards(
+    db: tauri::State<'_, Arc<Database>>,
+    board_id: String,
+) -> Result<Vec<Card>, AppError> {
+    let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+
+    db.with_connection(|conn| overdue_cards(conn, &board_id, &now))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn get_cards_due_between(
+    db: tauri::State<'_, Arc<Database>>,
+    board_id: String,
+    from: String,
+    to: String,
+) -> Result<Vec<Card>, AppError> {
+    let from = normalize_timestamp(&from)?;
+    let to = normalize_timestamp(&to)?;
+
+    db.with_connection(|conn| cards_due_between(conn, &board_id, &from, &to))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn get_archived_cards(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    board_id: String,
+    paging: Option<PageInput>,
+) -> Result<CardPage, AppError> {
+    let paging = paging.unwrap_or_default();
+    paging.validate(&limits)?;
+
+    db.with_connection(|conn| archived_cards(conn, &board_id, &paging))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn unarchive_cards(
+    db: tauri::State<'_, Arc<Database>>,
+    ids: Vec<String>,
+) -> Result<Vec<Card>, AppError> {
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| unarchive(tx, &ids, &now))
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub fn batch_update_card_orders(
     db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
     updates: Vec<BatchUpdateOrderInput>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    updates.validate(&limits)?;
     let now = Utc::now().to_rfc3339();
 
-    db.with_connection(|conn| {
-        for update in &updates {
-            conn.execute(
-                r#"UPDATE cards SET "order" = ?, updated_at = ? WHERE id = ?"#,
-                rusqlite::params![update.order, &now, &update.id],
-            )?;
-        }
-        Ok(())
-    })
-    .map_err(|e| e.to_string())
+    db.with_transaction(|tx| update_orders(tx, &updates, &now))
+        .map_err(AppError::from)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::test_helpers::test_helpers::create_test_db;
+    use crate::commands::trash::set_deleted_at;
+    use crate::db::test_helpers::test_helpers::{
+        card, create_test_db, seed_board, seed_board_with_card, seed_card, seed_column,
+    };
 
     #[test]
     fn test_create_card() {
@@ -465,22 +1177,9 @@ mod tests {
                 rusqlite::params!["Updated Title", "Updated description", &now, &card_id],
             )?;
 
-            let mut stmt = conn.prepare(
-                r#"SELECT id, column_id, title, description, "order", archived, created_at, updated_at FROM cards WHERE id = ?"#,
-            )?;
+            let mut stmt = conn.prepare(&format!("SELECT {CARD_COLUMNS} FROM cards WHERE id = ?"))?;
 
-            stmt.query_row([&card_id], |row| {
-                Ok(Card {
-                    id: row.get(0)?,
-                    column_id: row.get(1)?,
-                    title: row.get(2)?,
-                    description: row.get(3)?,
-                    order: row.get(4)?,
-                    archived: row.get::<_, i32>(5)? != 0,
-                    created_at: row.get(6)?,
-                    updated_at: row.get(7)?,
-                })
-            })
+            stmt.query_row([&card_id], Card::from_row)
         });
 
         assert!(result.is_ok());
@@ -533,22 +1232,9 @@ mod tests {
                 rusqlite::params![&col2_id, 5.0, &now, &card_id],
             )?;
 
-            let mut stmt = conn.prepare(
-                r#"SELECT id, column_id, title, description, "order", archived, created_at, updated_at FROM cards WHERE id = ?"#,
-            )?;
+            let mut stmt = conn.prepare(&format!("SELECT {CARD_COLUMNS} FROM cards WHERE id = ?"))?;
 
-            stmt.query_row([&card_id], |row| {
-                Ok(Card {
-                    id: row.get(0)?,
-                    column_id: row.get(1)?,
-                    title: row.get(2)?,
-                    description: row.get(3)?,
-                    order: row.get(4)?,
-                    archived: row.get::<_, i32>(5)? != 0,
-                    created_at: row.get(6)?,
-                    updated_at: row.get(7)?,
-                })
-            })
+            stmt.query_row([&card_id], Card::from_row)
         });
 
         assert!(result.is_ok());
@@ -592,27 +1278,15 @@ mod tests {
 
         // Get cards for board using the query
         let cards = db.with_connection(|conn| {
-            let mut stmt = conn.prepare(
-                r#"SELECT c.id, c.column_id, c.title, c.description, c."order", c.archived, c.created_at, c.updated_at
-                   FROM cards c
-                   INNER JOIN columns col ON c.column_id = col.id
-                   WHERE col.board_id = ? AND c.archived = 0
-                   ORDER BY c."order" ASC"#,
-            )?;
+            let mut stmt = conn.prepare(&format!(
+                r#"SELECT {CARD_COLUMNS}
+                   FROM cards
+                   WHERE column_id IN (SELECT id FROM columns WHERE board_id = ?) AND archived = 0
+                   ORDER BY "order" ASC"#
+            ))?;
 
             let cards = stmt
-                .query_map([&board_id], |row| {
-                    Ok(Card {
-                        id: row.get(0)?,
-                        column_id: row.get(1)?,
-                        title: row.get(2)?,
-                        description: row.get(3)?,
-                        order: row.get(4)?,
-                        archived: row.get::<_, i32>(5)? != 0,
-                        created_at: row.get(6)?,
-                        updated_at: row.get(7)?,
-                    })
-                })?
+                .query_map([&board_id], Card::from_row)?
                 .collect::<Result<Vec<_>, _>>()?;
 
             Ok(cards)
@@ -664,26 +1338,15 @@ mod tests {
 
         // Get cards for column 1
         let cards = db.with_connection(|conn| {
-            let mut stmt = conn.prepare(
-                r#"SELECT id, column_id, title, description, "order", archived, created_at, updated_at
+            let mut stmt = conn.prepare(&format!(
+                r#"SELECT {CARD_COLUMNS}
                    FROM cards
                    WHERE column_id = ? AND archived = 0
-                   ORDER BY "order" ASC"#,
-            )?;
+                   ORDER BY "order" ASC"#
+            ))?;
 
             let cards = stmt
-                .query_map([&col1_id], |row| {
-                    Ok(Card {
-                        id: row.get(0)?,
-                        column_id: row.get(1)?,
-                        title: row.get(2)?,
-                        description: row.get(3)?,
-                        order: row.get(4)?,
-                        archived: row.get::<_, i32>(5)? != 0,
-                        created_at: row.get(6)?,
-                        updated_at: row.get(7)?,
-                    })
-                })?
+                .query_map([&col1_id], Card::from_row)?
                 .collect::<Result<Vec<_>, _>>()?;
 
             Ok(cards)
@@ -746,26 +1409,15 @@ mod tests {
 
         // Verify orders were updated
         let cards = db.with_connection(|conn| {
-            let mut stmt = conn.prepare(
-                r#"SELECT id, column_id, title, description, "order", archived, created_at, updated_at
+            let mut stmt = conn.prepare(&format!(
+                r#"SELECT {CARD_COLUMNS}
                    FROM cards
                    WHERE column_id = ? AND archived = 0
-                   ORDER BY "order" ASC"#,
-            )?;
+                   ORDER BY "order" ASC"#
+            ))?;
 
             let cards = stmt
-                .query_map([&col_id], |row| {
-                    Ok(Card {
-                        id: row.get(0)?,
-                        column_id: row.get(1)?,
-                        title: row.get(2)?,
-                        description: row.get(3)?,
-                        order: row.get(4)?,
-                        archived: row.get::<_, i32>(5)? != 0,
-                        created_at: row.get(6)?,
-                        updated_at: row.get(7)?,
-                    })
-                })?
+                .query_map([&col_id], Card::from_row)?
                 .collect::<Result<Vec<_>, _>>()?;
 
             Ok(cards)
@@ -776,4 +1428,317 @@ mod tests {
         assert_eq!(cards[1].id, card1_id);
         assert_eq!(cards[1].order, 2.0);
     }
+
+    #[test]
+    fn test_normalize_timestamp() {
+        assert_eq!(
+            normalize_timestamp("2026-03-01T09:30:00.250+02:00").unwrap(),
+            "2026-03-01T07:30:00Z"
+        );
+        assert_eq!(
+            normalize_timestamp("2026-03-01T07:30:00Z").unwrap(),
+            "2026-03-01T07:30:00Z"
+        );
+        assert!(normalize_timestamp("next tuesday").is_err());
+    }
+
+    #[test]
+    fn test_due_date_queries() {
+        let (db, _temp) = create_test_db();
+
+        let board_id = db.with_connection(|conn| {
+            let board = seed_board(conn, "Board")?;
+            let column = seed_column(conn, &board.id, "Column", 1.0)?;
+
+            let due_dates = [
+                ("Last week", Some("2026-03-02T12:00:00Z")),
+                ("Monday", Some("2026-03-09T12:00:00Z")),
+                ("Friday", Some("2026-03-13T17:00:00Z")),
+                ("Next month", Some("2026-04-15T12:00:00Z")),
+                ("Someday", None),
+            ];
+            for (i, (title, due_at)) in due_dates.iter().enumerate() {
+                insert_card(conn, &Card { due_at: due_at.map(str::to_string), ..card(&column.id, title, i as f64) })?;
+            }
+
+            Ok(board.id)
+        }).unwrap();
+
+        let overdue = db.with_connection(|conn| {
+            overdue_cards(conn, &board_id, "2026-03-10T00:00:00Z")
+        }).unwrap();
+        let titles: Vec<_> = overdue.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["Last week", "Monday"]);
+
+        let this_week = db.with_connection(|conn| {
+            cards_due_between(conn, &board_id, "2026-03-09T00:00:00Z", "2026-03-15T23:59:59Z")
+        }).unwrap();
+        let titles: Vec<_> = this_week.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["Monday", "Friday"]);
+        assert_eq!(this_week[1].due_at.as_deref(), Some("2026-03-13T17:00:00Z"));
+    }
+
+    #[test]
+    fn test_archived_cards_paging_and_unarchive() {
+        let (db, _temp) = create_test_db();
+
+        let (board_id, card_ids) = db.with_connection(|conn| {
+            let board = seed_board(conn, "Board")?;
+            let column = seed_column(conn, &board.id, "Column", 1.0)?;
+
+            // Three archived cards with increasing updated_at, plus one live card
+            let mut card_ids = Vec::new();
+            for i in 0..4 {
+                let card = Card {
+                    archived: i < 3,
+                    updated_at: format!("2026-03-0{}T12:00:00Z", i + 1),
+                    ..card(&column.id, &format!("Card {}", i), i as f64)
+                };
+                insert_card(conn, &card)?;
+                card_ids.push(card.id);
+            }
+
+            Ok((board.id, card_ids))
+        }).unwrap();
+
+        let first = db.with_connection(|conn| {
+            archived_cards(conn, &board_id, &PageInput { offset: None, limit: Some(2) })
+        }).unwrap();
+        assert_eq!(first.total, 3);
+        let titles: Vec<_> = first.cards.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["Card 2", "Card 1"]);
+
+        let second = db.with_connection(|conn| {
+            archived_cards(conn, &board_id, &PageInput { offset: Some(2), limit: Some(2) })
+        }).unwrap();
+        assert_eq!(second.cards.len(), 1);
+        assert_eq!(second.cards[0].title, "Card 0");
+
+        // The live card is skipped
+        let now = Utc::now().to_rfc3339();
+        let restored = db.with_transaction(|tx| {
+            unarchive(tx, &[card_ids[0].clone(), card_ids[3].clone()], &now)
+        }).unwrap();
+        assert_eq!(restored.len(), 1);
+        assert!(!restored[0].archived);
+
+        let remaining = db.with_connection(|conn| archived_cards(conn, &board_id, &PageInput::default())).unwrap();
+        assert_eq!(remaining.total, 2);
+    }
+
+    fn column_with_cards(conn: &Connection, orders: &[f64]) -> rusqlite::Result<(String, Vec<String>)> {
+        let board = seed_board(conn, "Board")?;
+        let column = seed_column(conn, &board.id, "Column", 1.0)?;
+
+        let mut card_ids = Vec::new();
+        for (i, order) in orders.iter().enumerate() {
+            card_ids.push(seed_card(conn, &column.id, &format!("Card {}", i), *order)?.id);
+        }
+
+        Ok((column.id, card_ids))
+    }
+
+    fn titles_in(conn: &Connection, col_id: &str) -> rusqlite::Result<Vec<String>> {
+        conn.prepare(r#"SELECT title FROM cards WHERE column_id = ? ORDER BY "order" ASC"#)?
+            .query_map([col_id], |row| row.get(0))?
+            .collect()
+    }
+
+    #[test]
+    fn test_order_between() {
+        assert_eq!(order_between(None, None), Some(1.0));
+        assert_eq!(order_between(Some(2.0), None), Some(3.0));
+        assert_eq!(order_between(None, Some(2.0)), Some(1.0));
+        assert_eq!(order_between(Some(1.0), Some(2.0)), Some(1.5));
+        assert!(order_between(Some(1e-9), Some(2e-9)).is_some_and(|order| 1e-9 < order && order < 2e-9));
+        assert_eq!(order_between(Some(1.0), Some(1.0 + f64::EPSILON)), None);
+        assert_eq!(order_between(Some(1e17), None), None);
+    }
+
+    #[test]
+    fn test_move_between_uses_midpoint() {
+        let (db, _temp) = create_test_db();
+
+        let (col_id, ids) = db.with_connection(|conn| column_with_cards(conn, &[1.0, 2.0, 3.0])).unwrap();
+        let now = Utc::now().to_rfc3339();
+
+        // Card 2 between Card 0 and Card 1
+        let moved = db.with_transaction(|tx| {
+            move_between(tx, &ids[2], &col_id, Some(&ids[0]), Some(&ids[1]), &now)
+        }).unwrap().unwrap();
+        assert_eq!(moved.card.order, 1.5);
+
+        // Card 0 to the end
+        db.with_transaction(|tx| move_between(tx, &ids[0], &col_id, None, None, &now)).unwrap().unwrap();
+
+        let titles = db.with_connection(|conn| titles_in(conn, &col_id)).unwrap();
+        assert_eq!(titles, vec!["Card 2", "Card 1", "Card 0"]);
+
+        // Stale neighbours are rejected without writing anything
+        let stale = db.with_transaction(|tx| {
+            move_between(tx, &ids[1], &col_id, Some(&ids[0]), Some(&ids[2]), &now)
+        }).unwrap();
+        assert!(stale.is_none());
+    }
+
+    #[test]
+    fn test_move_between_renormalizes_collapsed_column() {
+        let (db, _temp) = create_test_db();
+
+        let (col_id, ids) = db.with_connection(|conn| column_with_cards(conn, &[1.0, 1.0 + f64::EPSILON, 5.0])).unwrap();
+        let now = Utc::now().to_rfc3339();
+
+        let moved = db.with_transaction(|tx| {
+            move_between(tx, &ids[2], &col_id, Some(&ids[0]), Some(&ids[1]), &now)
+        }).unwrap().unwrap();
+        assert_eq!(moved.card.order, 2.0);
+
+        let orders: Vec<f64> = db.with_connection(|conn| {
+            conn.prepare(r#"SELECT "order" FROM cards WHERE column_id = ? ORDER BY "order" ASC"#)?
+                .query_map([&col_id], |row| row.get(0))?
+                .collect()
+        }).unwrap();
+        assert_eq!(orders, vec![1.0, 2.0, 3.0]);
+
+        let titles = db.with_connection(|conn| titles_in(conn, &col_id)).unwrap();
+        assert_eq!(titles, vec!["Card 0", "Card 2", "Card 1"]);
+    }
+
+    #[test]
+    fn test_moves_stay_on_live_columns_of_the_card_board() {
+        let (db, _temp) = create_test_db();
+        let now = Utc::now().to_rfc3339();
+
+        let (col_id, ids) = db.with_connection(|conn| column_with_cards(conn, &[1.0])).unwrap();
+        let (other_col_id, _) = db.with_connection(|conn| column_with_cards(conn, &[])).unwrap();
+
+        assert!(matches!(
+            db.with_transaction(|tx| move_to(tx, &ids[0], &other_col_id, None, 1.0, &now)),
+            Err(DbError::WrongBoard { kind: "column", .. })
+        ));
+        assert!(matches!(
+            db.with_transaction(|tx| move_between(tx, &ids[0], &other_col_id, None, None, &now)),
+            Err(DbError::WrongBoard { kind: "column", .. })
+        ));
+
+        let trashed_id = db.with_connection(|conn| {
+            let board_id = board_id_for_column(conn, &col_id)?;
+            let trashed = seed_column(conn, &board_id, "Trashed", 2.0)?;
+            set_deleted_at(conn, TrashKind::Column, &trashed.id, Some(&now))?;
+            Ok(trashed.id)
+        }).unwrap();
+        assert!(matches!(
+            db.with_transaction(|tx| move_to(tx, &ids[0], &trashed_id, None, 1.0, &now)),
+            Err(DbError::NotFound { kind: "column", .. })
+        ));
+
+        assert!(matches!(
+            db.with_transaction(|tx| move_between(tx, &ids[0], &trashed_id, None, None, &now)),
+            Err(DbError::NotFound { kind: "column", .. })
+        ));
+
+        let card = db.with_connection(|conn| fetch_card(conn, &ids[0])).unwrap().unwrap();
+        assert_eq!(card.column_id, col_id);
+    }
+
+    #[test]
+    fn test_trashed_and_archived_cards_stay_put() {
+        let (db, _temp) = create_test_db();
+        let now = Utc::now().to_rfc3339();
+
+        let (col_id, ids) = db.with_connection(|conn| column_with_cards(conn, &[1.0, 2.0])).unwrap();
+        let target_id = db.with_connection(|conn| {
+            let board_id = board_id_for_column(conn, &col_id)?;
+            set_deleted_at(conn, TrashKind::Card, &ids[0], Some(&now))?;
+            conn.execute("UPDATE cards SET archived = 1 WHERE id = ?", [&ids[1]])?;
+            Ok(seed_column(conn, &board_id, "Target", 2.0)?.id)
+        }).unwrap();
+
+        for id in &ids {
+            assert!(matches!(
+                db.with_transaction(|tx| move_to(tx, id, &target_id, None, 1.0, &now)),
+                Err(DbError::NotFound { kind: "card", .. })
+            ));
+            assert!(db.with_transaction(|tx| move_between(tx, id, &target_id, None, None, &now)).unwrap().is_none());
+
+            let card = db.with_connection(|conn| fetch_card(conn, id)).unwrap().unwrap();
+            assert_eq!(card.column_id, col_id);
+        }
+        let entries: i64 = db
+            .with_connection(|conn| conn.query_row("SELECT COUNT(*) FROM history", [], |row| row.get(0)))
+            .unwrap();
+        assert_eq!(entries, 0);
+    }
+
+    #[test]
+    fn test_batch_update_rolls_back_on_missing_card() {
+        let (db, _temp) = create_test_db();
+
+        let (col_id, ids) = db.with_connection(|conn| column_with_cards(conn, &[1.0, 2.0])).unwrap();
+        let now = Utc::now().to_rfc3339();
+        let updates = vec![
+            BatchUpdateOrderInput { id: ids[0].clone(), order: 3.0 },
+            BatchUpdateOrderInput { id: "missing".to_string(), order: 4.0 },
+        ];
+
+        let err = db.with_transaction(|tx| update_orders(tx, &updates, &now)).unwrap_err();
+        assert!(matches!(err, DbError::NotFound { kind: "card", ref id } if id == "missing"));
+
+        let titles = db.with_connection(|conn| titles_in(conn, &col_id)).unwrap();
+        assert_eq!(titles, vec!["Card 0", "Card 1"]);
+    }
+
+    #[test]
+    fn test_batch_update_rejects_cards_from_two_boards() {
+        let (db, _temp) = create_test_db();
+
+        let (col_id, ids) = db.with_connection(|conn| column_with_cards(conn, &[1.0, 2.0])).unwrap();
+        let (_, _, other) = db.with_connection(seed_board_with_card).unwrap();
+        let now = Utc::now().to_rfc3339();
+        let updates = vec![
+            BatchUpdateOrderInput { id: ids[0].clone(), order: 3.0 },
+            BatchUpdateOrderInput { id: other.id.clone(), order: 4.0 },
+        ];
+
+        let err = db.with_transaction(|tx| update_orders(tx, &updates, &now)).unwrap_err();
+        assert!(matches!(err, DbError::WrongBoard { kind: "card", ref id } if *id == other.id));
+        assert_eq!(AppError::from(err).code(), "validation");
+
+        let titles = db.with_connection(|conn| titles_in(conn, &col_id)).unwrap();
+        assert_eq!(titles, vec!["Card 0", "Card 1"]);
+        let entries: i64 = db
+            .with_connection(|conn| conn.query_row("SELECT COUNT(*) FROM history", [], |row| row.get(0)))
+            .unwrap();
+        assert_eq!(entries, 0);
+    }
+
+    #[test]
+    fn test_update_input_tells_null_from_missing() {
+        let input: UpdateCardInput = serde_json::from_str(r#"{"priority": "urgent"}"#).unwrap();
+        assert_eq!(input.priority, Some(Some(Priority::Urgent)));
+        assert_eq!(input.estimate, None);
+
+        let input: UpdateCardInput = serde_json::from_str(r#"{"priority": null, "estimate": 5}"#).unwrap();
+        assert_eq!(input.priority, Some(None));
+        assert_eq!(input.estimate, Some(Some(5.0)));
+        assert_eq!(input.due_at, None);
+
+        let input: UpdateCardInput =
+            serde_json::from_str(r#"{"dueAt": null, "startAt": "2026-03-01T09:00:00Z"}"#).unwrap();
+        assert_eq!(input.due_at, Some(None));
+        assert_eq!(input.start_at, Some(Some("2026-03-01T09:00:00Z".to_string())));
+
+        let (db, _temp) = create_test_db();
+        let (_col_id, ids) = db.with_connection(|conn| column_with_cards(conn, &[1.0])).unwrap();
+        db.with_connection(|conn| {
+            conn.execute(
+                "UPDATE cards SET priority = ?, estimate = ? WHERE id = ?",
+                rusqlite::params![Priority::High, 2.5, &ids[0]],
+            )
+        }).unwrap();
+
+        let card = db.with_connection(|conn| fetch_card(conn, &ids[0])).unwrap().unwrap();
+        assert_eq!(card.priority, Some(Priority::High));
+        assert_eq!(card.estimate, Some(2.5));
+    }
 }
@@ -0,0 +1,281 @@
+use crate::db::{Database, DbError};
+use crate::error::AppError;
+use crate::validation::{Limits, Validate};
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistItem {
+    pub id: String,
+    pub card_id: String,
+    pub text: String,
+    pub done: bool,
+    pub order: f64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Progress shown on a card's face. Derived from `checklist_items`, so it is
+/// ignored when a card is written back.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistSummary {
+    pub done: i64,
+    pub total: i64,
+}
+
+/// Column list matching `ChecklistItem::from_row`.
+pub(crate) const ITEM_COLUMNS: &str = r#"id, card_id, text, done, "order", created_at, updated_at"#;
+
+impl ChecklistItem {
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(ChecklistItem {
+            id: row.get(0)?,
+            card_id: row.get(1)?,
+            text: row.get(2)?,
+            done: row.get::<_, i32>(3)? != 0,
+            order: row.get(4)?,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddChecklistItemInput {
+    pub card_id: String,
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderChecklistItemInput {
+    pub id: String,
+    pub order: f64,
+}
+
+fn fetch_item(conn: &Connection, id: &str) -> rusqlite::Result<Option<ChecklistItem>> {
+    conn.query_row(
+        &format!("SELECT {ITEM_COLUMNS} FROM checklist_items WHERE id = ?"),
+        [id],
+        ChecklistItem::from_row,
+    )
+    .optional()
+}
+
+pub(crate) fn items_for_card(conn: &Connection, card_id: &str) -> rusqlite::Result<Vec<ChecklistItem>> {
+    let mut stmt = conn.prepare(&format!(
+        r#"SELECT {ITEM_COLUMNS}
+           FROM checklist_items
+           WHERE card_id = ?
+           ORDER BY "order" ASC"#
+    ))?;
+
+    let items = stmt
+        .query_map([card_id], ChecklistItem::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(items)
+}
+
+/// Appends an item to the end of a card's checklist.
+pub(crate) fn add_item(conn: &Connection, card_id: &str, text: &str, now: &str) -> rusqlite::Result<ChecklistItem> {
+    let max_order: Option<f64> = conn.query_row(
+        r#"SELECT MAX("order") FROM checklist_items WHERE card_id = ?"#,
+        [card_id],
+        |row| row.get(0),
+    )?;
+
+    let item = ChecklistItem {
+        id: Uuid::new_v4().to_string(),
+        card_id: card_id.to_string(),
+        text: text.to_string(),
+        done: false,
+        order: max_order.unwrap_or(0.0) + 1.0,
+        created_at: now.to_string(),
+        updated_at: now.to_string(),
+    };
+    insert_item(conn, &item)?;
+    Ok(item)
+}
+
+pub(crate) fn insert_item(conn: &Connection, item: &ChecklistItem) -> rusqlite::Result<()> {
+    conn.execute(
+        r#"INSERT INTO checklist_items (id, card_id, text, done, "order", created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+        rusqlite::params![
+            &item.id,
+            &item.card_id,
+            &item.text,
+            item.done as i32,
+            item.order,
+            &item.created_at,
+            &item.updated_at
+        ],
+    )?;
+    Ok(())
+}
+
+/// Flips an item between done and not done. Returns `None` if it doesn't exist.
+pub(crate) fn toggle_item(conn: &Connection, id: &str, now: &str) -> rusqlite::Result<Option<ChecklistItem>> {
+    conn.execute(
+        "UPDATE checklist_items SET done = 1 - done, updated_at = ? WHERE id = ?",
+        [now, id],
+    )?;
+    fetch_item(conn, id)
+}
+
+/// Applies every order update or none of them, like `columns::reorder`.
+pub(crate) fn reorder(conn: &Connection, updates: &[ReorderChecklistItemInput], now: &str) -> Result<(), DbError> {
+    for update in updates {
+        let changed = conn
+            .execute(
+                r#"UPDATE checklist_items SET "order" = ?, updated_at = ? WHERE id = ?"#,
+                rusqlite::params![update.order, now, &update.id],
+            )
+            .map_err(|source| DbError::ItemFailed { kind: "checklist item", id: update.id.clone(), source })?;
+        if changed == 0 {
+            return Err(DbError::NotFound { kind: "checklist item", id: update.id.clone() });
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_checklist(
+    db: tauri::State<'_, Arc<Database>>,
+    card_id: String,
+) -> Result<Vec<ChecklistItem>, AppError> {
+    db.with_connection(|conn| items_for_card(conn, &card_id))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn add_checklist_item(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    input: AddChecklistItemInput,
+) -> Result<ChecklistItem, AppError> {
+    input.validate(&limits)?;
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| Ok(add_item(tx, &input.card_id, &input.text, &now)?))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn toggle_checklist_item(
+    db: tauri::State<'_, Arc<Database>>,
+    id: String,
+) -> Result<ChecklistItem, AppError> {
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| {
+        toggle_item(tx, &id, &now)?.ok_or_else(|| DbError::NotFound { kind: "checklist item", id: id.clone() })
+    })
+    .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn reorder_checklist_items(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    updates: Vec<ReorderChecklistItemInput>,
+) -> Result<(), AppError> {
+    updates.validate(&limits)?;
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| reorder(tx, &updates, &now))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn delete_checklist_item(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), AppError> {
+    db.with_connection(|conn| {
+        conn.execute("DELETE FROM checklist_items WHERE id = ?", [&id])?;
+        Ok(())
+    })
+    .map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cards::fetch_card;
+    use crate::db::test_helpers::test_helpers::{create_test_db, seed_board_with_card};
+
+    fn seed_card(conn: &Connection) -> rusqlite::Result<String> {
+        let (_, _, card) = seed_board_with_card(conn)?;
+        Ok(card.id)
+    }
+
+    #[test]
+    fn test_add_toggle_and_summary() {
+        let (db, _temp) = create_test_db();
+
+        let card_id = db.with_connection(seed_card).unwrap();
+        let now = Utc::now().to_rfc3339();
+
+        let first = db.with_connection(|conn| add_item(conn, &card_id, "Write spec", &now)).unwrap();
+        let second = db.with_connection(|conn| add_item(conn, &card_id, "Ship it", &now)).unwrap();
+        assert_eq!(first.order, 1.0);
+        assert_eq!(second.order, 2.0);
+
+        let toggled = db.with_connection(|conn| toggle_item(conn, &first.id, &now)).unwrap().unwrap();
+        assert!(toggled.done);
+
+        let card = db.with_connection(|conn| fetch_card(conn, &card_id)).unwrap().unwrap();
+        assert_eq!(card.checklist, ChecklistSummary { done: 1, total: 2 });
+
+        let toggled = db.with_connection(|conn| toggle_item(conn, &first.id, &now)).unwrap().unwrap();
+        assert!(!toggled.done);
+        assert!(db.with_connection(|conn| toggle_item(conn, "missing", &now)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_reorder_is_all_or_nothing() {
+        let (db, _temp) = create_test_db();
+
+        let card_id = db.with_connection(seed_card).unwrap();
+        let now = Utc::now().to_rfc3339();
+
+        let first = db.with_connection(|conn| add_item(conn, &card_id, "First", &now)).unwrap();
+        db.with_connection(|conn| add_item(conn, &card_id, "Second", &now)).unwrap();
+
+        let updates = vec![
+            ReorderChecklistItemInput { id: first.id.clone(), order: 3.0 },
+            ReorderChecklistItemInput { id: "missing".to_string(), order: 4.0 },
+        ];
+        let err = db.with_transaction(|tx| reorder(tx, &updates, &now)).unwrap_err();
+        assert!(matches!(err, DbError::NotFound { kind: "checklist item", .. }));
+
+        let texts = |db: &Database| -> Vec<String> {
+            db.with_connection(|conn| items_for_card(conn, &card_id))
+                .unwrap()
+                .into_iter()
+                .map(|item| item.text)
+                .collect()
+        };
+        assert_eq!(texts(&db), vec!["First", "Second"]);
+
+        let updates = vec![ReorderChecklistItemInput { id: first.id.clone(), order: 3.0 }];
+        db.with_transaction(|tx| reorder(tx, &updates, &now)).unwrap();
+        assert_eq!(texts(&db), vec!["Second", "First"]);
+    }
+
+    #[test]
+    fn test_items_are_removed_with_their_card() {
+        let (db, _temp) = create_test_db();
+
+        let card_id = db.with_connection(seed_card).unwrap();
+        let now = Utc::now().to_rfc3339();
+        db.with_connection(|conn| add_item(conn, &card_id, "Orphan", &now)).unwrap();
+
+        db.with_connection(|conn| conn.execute("DELETE FROM cards WHERE id = ?", [&card_id])).unwrap();
+        assert!(db.with_connection(|conn| items_for_card(conn, &card_id)).unwrap().is_empty());
+    }
+}
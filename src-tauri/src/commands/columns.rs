@@ -1,5 +1,11 @@
-use crate::db::Database;
+use crate::commands::boards::fetch_live_board;
+use crate::commands::history::{self, Op};
+use crate::commands::trash::{self, TrashKind};
+use crate::db::{Database, DbError};
+use crate::error::AppError;
+use crate::validation::{Limits, Validate};
 use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, Row};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
@@ -14,6 +20,165 @@ pub struct Column {
     pub archived: bool,
     pub created_at: String,
     pub updated_at: String,
+    /// Most cards the column should hold at once. `None` means no limit.
+    #[serde(default)]
+    pub wip_limit: Option<i64>,
+    #[serde(default)]
+    pub wip_policy: WipPolicy,
+    /// Cards in a done column count as finished, e.g. they no longer block others.
+    #[serde(default)]
+    pub done: bool,
+}
+
+/// What happens when a card would take a column past its WIP limit.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum WipPolicy {
+    /// Let the card in and report that the limit was exceeded.
+    #[default]
+    Warn,
+    /// Refuse the card.
+    Block,
+}
+
+impl WipPolicy {
+    fn as_str(self) -> &'static str {
+        match self {
+            WipPolicy::Warn => "warn",
+            WipPolicy::Block => "block",
+        }
+    }
+
+    fn parse(policy: &str) -> Option<Self> {
+        match policy {
+            "warn" => Some(WipPolicy::Warn),
+            "block" => Some(WipPolicy::Block),
+            _ => None,
+        }
+    }
+}
+
+/// Column list matching `Column::from_row`.
+pub(crate) const COLUMN_COLUMNS: &str =
+    r#"id, board_id, name, "order", archived, created_at, updated_at, wip_limit, wip_policy, done"#;
+
+impl Column {
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let wip_policy: String = row.get(8)?;
+
+        Ok(Column {
+            id: row.get(0)?,
+            board_id: row.get(1)?,
+            name: row.get(2)?,
+            order: row.get(3)?,
+            archived: row.get::<_, i32>(4)? != 0,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+            wip_limit: row.get(7)?,
+            wip_policy: WipPolicy::parse(&wip_policy).ok_or_else(|| {
+                rusqlite::Error::FromSqlConversionFailure(8, rusqlite::types::Type::Text, wip_policy.into())
+            })?,
+            done: row.get::<_, i32>(9)? != 0,
+        })
+    }
+}
+
+/// A column holding more cards than its WIP limit allows.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WipExceeded {
+    pub column_id: String,
+    pub column_name: String,
+    pub limit: i64,
+    pub count: i64,
+}
+
+/// Checks a column's WIP limit after a card has been put into it. Returns
+/// the overrun for a `Warn` column and fails with `DbError::WipLimit` for a
+/// `Block` one, which rolls the surrounding transaction back.
+pub(crate) fn check_wip_limit(conn: &Connection, column_id: &str) -> Result<Option<WipExceeded>, DbError> {
+    let Some(column) = fetch_column(conn, column_id)? else {
+        return Ok(None);
+    };
+    let Some(limit) = column.wip_limit else {
+        return Ok(None);
+    };
+
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM cards WHERE column_id = ? AND archived = 0 AND deleted_at IS NULL",
+        [column_id],
+        |row| row.get(0),
+    )?;
+    if count <= limit {
+        return Ok(None);
+    }
+
+    match column.wip_policy {
+        WipPolicy::Warn => Ok(Some(WipExceeded { column_id: column.id, column_name: column.name, limit, count })),
+        WipPolicy::Block => Err(DbError::WipLimit { column: column.name, limit }),
+    }
+}
+
+pub(crate) fn fetch_column(conn: &Connection, id: &str) -> rusqlite::Result<Option<Column>> {
+    conn.query_row(
+        &format!("SELECT {COLUMN_COLUMNS} FROM columns WHERE id = ?"),
+        [id],
+        Column::from_row,
+    )
+    .optional()
+}
+
+pub(crate) fn board_id_for_column(conn: &Connection, column_id: &str) -> rusqlite::Result<String> {
+    conn.query_row("SELECT board_id FROM columns WHERE id = ?", [column_id], |row| row.get(0))
+}
+
+/// The board a column belongs to, or `None` if it doesn't exist, is archived
+/// or is in the trash.
+pub(crate) fn live_column_board(conn: &Connection, column_id: &str) -> rusqlite::Result<Option<String>> {
+    conn.query_row(
+        "SELECT board_id FROM columns WHERE id = ? AND archived = 0 AND deleted_at IS NULL",
+        [column_id],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+pub(crate) fn insert_column(conn: &Connection, column: &Column) -> rusqlite::Result<()> {
+    write_column(conn, column, "")
+}
+
+/// Inserts the column or overwrites the existing row in place.
+pub(crate) fn upsert_column(conn: &Connection, column: &Column) -> rusqlite::Result<()> {
+    write_column(
+        conn,
+        column,
+        r#"ON CONFLICT(id) DO UPDATE SET board_id = excluded.board_id, name = excluded.name,
+               "order" = excluded."order", archived = excluded.archived,
+               created_at = excluded.created_at, updated_at = excluded.updated_at,
+               wip_limit = excluded.wip_limit, wip_policy = excluded.wip_policy, done = excluded.done"#,
+    )
+}
+
+fn write_column(conn: &Connection, column: &Column, on_conflict: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        &format!(
+            r#"INSERT INTO columns (id, board_id, name, "order", archived, created_at, updated_at, wip_limit, wip_policy, done)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) {on_conflict}"#
+        ),
+        rusqlite::params![
+            &column.id,
+            &column.board_id,
+            &column.name,
+            &column.order,
+            column.archived as i32,
+            &column.created_at,
+            &column.updated_at,
+            column.wip_limit,
+            column.wip_policy.as_str(),
+            column.done as i32
+        ],
+    )?;
+    Ok(())
 }
 
 #[derive(Debug, Deserialize)]
@@ -30,6 +195,13 @@ pub struct UpdateColumnInput {
     pub name: Option<String>,
     pub order: Option<f64>,
     pub archived: Option<bool>,
+    /// Zero removes the limit.
+    #[serde(default)]
+    pub wip_limit: Option<i64>,
+    #[serde(default)]
+    pub wip_policy: Option<WipPolicy>,
+    #[serde(default)]
+    pub done: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,101 +211,202 @@ pub struct ReorderColumnInput {
     pub order: f64,
 }
 
+/// Live (not archived or trashed) columns on a board, in display order.
+pub(crate) fn columns_for_board(conn: &Connection, board_id: &str) -> rusqlite::Result<Vec<Column>> {
+    let mut stmt = conn.prepare(&format!(
+        r#"SELECT {COLUMN_COLUMNS}
+           FROM columns
+           WHERE board_id = ? AND archived = 0 AND deleted_at IS NULL
+           ORDER BY "order" ASC"#
+    ))?;
+
+    let columns = stmt
+        .query_map([board_id], Column::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(columns)
+}
+
+pub(crate) fn archived_columns(conn: &Connection, board_id: &str) -> rusqlite::Result<Vec<Column>> {
+    let mut stmt = conn.prepare(&format!(
+        r#"SELECT {COLUMN_COLUMNS}
+           FROM columns
+           WHERE board_id = ? AND archived = 1 AND deleted_at IS NULL
+           ORDER BY "order" ASC"#
+    ))?;
+
+    let columns = stmt
+        .query_map([board_id], Column::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(columns)
+}
+
+/// Applies every order update or none of them. The error names the first
+/// column that was missing or failed to update.
+pub(crate) fn reorder(conn: &Connection, updates: &[ReorderColumnInput], now: &str) -> Result<(), DbError> {
+    let mut before = Vec::new();
+    let mut after = Vec::new();
+
+    for update in updates {
+        let failed = |source| DbError::ItemFailed { kind: "column", id: update.id.clone(), source };
+
+        let column = fetch_column(conn, &update.id)
+            .map_err(failed)?
+            .ok_or_else(|| DbError::NotFound { kind: "column", id: update.id.clone() })?;
+        conn.execute(
+            r#"UPDATE columns SET "order" = ?, updated_at = ? WHERE id = ?"#,
+            rusqlite::params![update.order, now, &update.id],
+        )
+        .map_err(failed)?;
+
+        after.push(Op::PutColumn {
+            column: Column { order: update.order, updated_at: now.to_string(), ..column.clone() },
+        });
+        before.push(Op::PutColumn { column });
+    }
+
+    if let Some(Op::PutColumn { column }) = before.first() {
+        let board_id = column.board_id.clone();
+        history::record(conn, &board_id, "reorder_columns", "Reorder columns", &after, &before)?;
+    }
+
+    Ok(())
+}
+
+/// Clears the archived flag on each column and journals it as one change.
+/// Returns the columns that were actually unarchived.
+pub(crate) fn unarchive(conn: &Connection, ids: &[String], now: &str) -> rusqlite::Result<Vec<Column>> {
+    let mut before = Vec::new();
+    let mut after = Vec::new();
+
+    for id in ids {
+        let Some(column) = fetch_column(conn, id)?.filter(|c| c.archived) else {
+            continue;
+        };
+        conn.execute(
+            "UPDATE columns SET archived = 0, updated_at = ? WHERE id = ?",
+            rusqlite::params![now, id],
+        )?;
+        after.push(Column { archived: false, updated_at: now.to_string(), ..column.clone() });
+        before.push(column);
+    }
+
+    if let Some(column) = before.first() {
+        let board_id = column.board_id.clone();
+        history::record(
+            conn,
+            &board_id,
+            "unarchive_columns",
+            &format!("Unarchive {} column(s)", after.len()),
+            &after.iter().cloned().map(|column| Op::PutColumn { column }).collect::<Vec<_>>(),
+            &before.into_iter().map(|column| Op::PutColumn { column }).collect::<Vec<_>>(),
+        )?;
+    }
+
+    Ok(after)
+}
+
 #[tauri::command]
 pub fn get_columns_for_board(
     db: tauri::State<'_, Arc<Database>>,
     board_id: String,
-) -> Result<Vec<Column>, String> {
-    db.with_connection(|conn| {
-        let mut stmt = conn.prepare(
-            r#"SELECT id, board_id, name, "order", archived, created_at, updated_at
-               FROM columns
-               WHERE board_id = ? AND archived = 0
-               ORDER BY "order" ASC"#,
-        )?;
-
-        let columns = stmt
-            .query_map([&board_id], |row| {
-                Ok(Column {
-                    id: row.get(0)?,
-                    board_id: row.get(1)?,
-                    name: row.get(2)?,
-                    order: row.get(3)?,
-                    archived: row.get::<_, i32>(4)? != 0,
-                    created_at: row.get(5)?,
-                    updated_at: row.get(6)?,
-                })
-            })?
-            .collect::<Result<Vec<_>, _>>()?;
+) -> Result<Vec<Column>, AppError> {
+    db.with_connection(|conn| columns_for_board(conn, &board_id))
+        .map_err(AppError::from)
+}
 
-        Ok(columns)
-    })
-    .map_err(|e| e.to_string())
+#[tauri::command]
+pub fn get_archived_columns(
+    db: tauri::State<'_, Arc<Database>>,
+    board_id: String,
+) -> Result<Vec<Column>, AppError> {
+    db.with_connection(|conn| archived_columns(conn, &board_id))
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn create_column(
+pub fn unarchive_columns(
     db: tauri::State<'_, Arc<Database>>,
-    input: CreateColumnInput,
-) -> Result<Column, String> {
-    let id = Uuid::new_v4().to_string();
+    ids: Vec<String>,
+) -> Result<Vec<Column>, AppError> {
     let now = Utc::now().to_rfc3339();
 
+    db.with_transaction(|tx| Ok(unarchive(tx, &ids, &now)?))
+        .map_err(AppError::from)
+}
+
+/// Adds a column to a live board, at the end unless the input gives an order.
+pub(crate) fn create(conn: &Connection, input: &CreateColumnInput, now: &str) -> Result<Column, DbError> {
+    if fetch_live_board(conn, &input.board_id)?.is_none() {
+        return Err(DbError::NotFound { kind: "board", id: input.board_id.clone() });
+    }
+
     // Get the max order for this board if order not provided
     let order = if let Some(o) = input.order {
         o
     } else {
-        db.with_connection(|conn| {
-            let max_order: Option<f64> = conn.query_row(
-                r#"SELECT MAX("order") FROM columns WHERE board_id = ?"#,
-                [&input.board_id],
-                |row| row.get(0),
-            )?;
-            Ok(max_order.unwrap_or(0.0) + 1.0)
-        })
-        .map_err(|e| e.to_string())?
+        let max_order: Option<f64> = conn.query_row(
+            r#"SELECT MAX("order") FROM columns WHERE board_id = ?"#,
+            [&input.board_id],
+            |row| row.get(0),
+        )?;
+        max_order.unwrap_or(0.0) + 1.0
     };
 
     let column = Column {
-        id: id.clone(),
-        board_id: input.board_id,
-        name: input.name,
+        id: Uuid::new_v4().to_string(),
+        board_id: input.board_id.clone(),
+        name: input.name.clone(),
         order,
         archived: false,
-        created_at: now.clone(),
-        updated_at: now,
+        created_at: now.to_string(),
+        updated_at: now.to_string(),
+        wip_limit: None,
+        wip_policy: WipPolicy::default(),
+        done: false,
     };
 
-    db.with_connection(|conn| {
-        conn.execute(
-            r#"INSERT INTO columns (id, board_id, name, "order", archived, created_at, updated_at)
-               VALUES (?, ?, ?, ?, ?, ?, ?)"#,
-            rusqlite::params![
-                &column.id,
-                &column.board_id,
-                &column.name,
-                &column.order,
-                column.archived as i32,
-                &column.created_at,
-                &column.updated_at
-            ],
-        )?;
-        Ok(())
-    })
-    .map_err(|e| e.to_string())?;
+    insert_column(conn, &column)?;
+    history::record(
+        conn,
+        &column.board_id,
+        "create_column",
+        &format!("Add column \"{}\"", column.name),
+        &[Op::PutColumn { column: column.clone() }, Op::untrash(TrashKind::Column, &column.id)],
+        &[Op::trash(TrashKind::Column, &column.id)],
+    )?;
 
     Ok(column)
 }
 
+#[tauri::command]
+pub fn create_column(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    input: CreateColumnInput,
+) -> Result<Column, AppError> {
+    input.validate(&limits)?;
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| create(tx, &input, &now))
+        .map_err(AppError::from)
+}
+
 #[tauri::command]
 pub fn update_column(
     db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
     id: String,
     input: UpdateColumnInput,
-) -> Result<Column, String> {
+) -> Result<Column, AppError> {
+    input.validate(&limits)?;
     let now = Utc::now().to_rfc3339();
 
-    db.with_connection(|conn| {
+    db.with_transaction(|tx| {
+        let before = fetch_column(tx, &id)?
+            .ok_or_else(|| DbError::NotFound { kind: "column", id: id.clone() })?;
+
         // Build dynamic update query
         let mut updates = vec!["updated_at = ?"];
         let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(now.clone())];
@@ -150,6 +423,18 @@ pub fn update_column(
             updates.push("archived = ?");
             params.push(Box::new(archived as i32));
         }
+        if let Some(wip_limit) = input.wip_limit {
+            updates.push("wip_limit = ?");
+            params.push(Box::new((wip_limit > 0).then_some(wip_limit)));
+        }
+        if let Some(wip_policy) = input.wip_policy {
+            updates.push("wip_policy = ?");
+            params.push(Box::new(wip_policy.as_str()));
+        }
+        if let Some(done) = input.done {
+            updates.push("done = ?");
+            params.push(Box::new(done as i32));
+        }
 
         params.push(Box::new(id.clone()));
 
@@ -159,61 +444,69 @@ pub fn update_column(
         );
 
         let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
-        conn.execute(&query, params_refs.as_slice())?;
+        tx.execute(&query, params_refs.as_slice())?;
 
         // Fetch updated column
-        let mut stmt = conn.prepare(
-            r#"SELECT id, board_id, name, "order", archived, created_at, updated_at
-               FROM columns WHERE id = ?"#,
+        let column = tx.query_row(
+            &format!("SELECT {COLUMN_COLUMNS} FROM columns WHERE id = ?"),
+            [&id],
+            Column::from_row,
         )?;
 
-        stmt.query_row([&id], |row| {
-            Ok(Column {
-                id: row.get(0)?,
-                board_id: row.get(1)?,
-                name: row.get(2)?,
-                order: row.get(3)?,
-                archived: row.get::<_, i32>(4)? != 0,
-                created_at: row.get(5)?,
-                updated_at: row.get(6)?,
-            })
-        })
+        history::record(
+            tx,
+            &column.board_id,
+            "update_column",
+            &format!("Edit column \"{}\"", column.name),
+            &[Op::PutColumn { column: column.clone() }],
+            &[Op::PutColumn { column: before }],
+        )?;
+
+        Ok(column)
     })
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub fn delete_column(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), String> {
-    db.with_connection(|conn| {
-        conn.execute("DELETE FROM columns WHERE id = ?", [&id])?;
+pub fn delete_column(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), AppError> {
+    db.with_transaction(|tx| {
+        let Some(column) = fetch_column(tx, &id)? else {
+            return Ok(());
+        };
+
+        // Cards stay attached to the trashed column and come back with it
+        trash::move_to_trash(
+            tx,
+            TrashKind::Column,
+            &id,
+            &column.board_id,
+            &format!("Delete column \"{}\"", column.name),
+        )?;
         Ok(())
     })
-    .map_err(|e| e.to_string())
+    .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub fn reorder_columns(
     db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
     updates: Vec<ReorderColumnInput>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    updates.validate(&limits)?;
     let now = Utc::now().to_rfc3339();
 
-    db.with_connection(|conn| {
-        for update in &updates {
-            conn.execute(
-                r#"UPDATE columns SET "order" = ?, updated_at = ? WHERE id = ?"#,
-                rusqlite::params![update.order, &now, &update.id],
-            )?;
-        }
-        Ok(())
-    })
-    .map_err(|e| e.to_string())
+    db.with_transaction(|tx| reorder(tx, &updates, &now))
+        .map_err(AppError::from)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::test_helpers::test_helpers::create_test_db;
+    use crate::commands::cards::{self, fetch_card, insert_card, move_to, Card};
+    use crate::commands::history::undo_last;
+    use crate::commands::trash::{restore, set_deleted_at, TrashKind};
+    use crate::db::test_helpers::test_helpers::{card, column, create_test_db, seed_board, seed_card, seed_column};
 
     #[test]
     fn test_create_column() {
@@ -331,6 +624,9 @@ mod tests {
                     archived: row.get::<_, i32>(4)? != 0,
                     created_at: row.get(5)?,
                     updated_at: row.get(6)?,
+                    wip_limit: None,
+                    wip_policy: WipPolicy::default(),
+                    done: false,
                 })
             })
         });
@@ -415,6 +711,9 @@ mod tests {
                         archived: row.get::<_, i32>(4)? != 0,
                         created_at: row.get(5)?,
                         updated_at: row.get(6)?,
+                        wip_limit: None,
+                        wip_policy: WipPolicy::default(),
+                        done: false,
                     })
                 })?
                 .collect::<Result<Vec<_>, _>>()?;
@@ -479,6 +778,9 @@ mod tests {
                         archived: row.get::<_, i32>(4)? != 0,
                         created_at: row.get(5)?,
                         updated_at: row.get(6)?,
+                        wip_limit: None,
+                        wip_policy: WipPolicy::default(),
+                        done: false,
                     })
                 })?
                 .collect::<Result<Vec<_>, _>>()?;
@@ -494,4 +796,153 @@ mod tests {
         assert!(columns[0].order < columns[1].order);
         assert!(columns[1].order < columns[2].order);
     }
+
+    #[test]
+    fn test_archived_columns_and_unarchive() {
+        let (db, _temp) = create_test_db();
+
+        let (board_id, live_id, archived_id) = db.with_connection(|conn| {
+            let board = seed_board(conn, "Board")?;
+            let live = seed_column(conn, &board.id, "Live", 1.0)?;
+            let archived = Column { archived: true, ..column(&board.id, "Old", 2.0) };
+            insert_column(conn, &archived)?;
+            Ok((board.id, live.id, archived.id))
+        }).unwrap();
+
+        let archived = db.with_connection(|conn| archived_columns(conn, &board_id)).unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].id, archived_id);
+
+        let now = Utc::now().to_rfc3339();
+        let restored = db.with_connection(|conn| unarchive(conn, &[live_id.clone(), archived_id.clone()], &now)).unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].id, archived_id);
+
+        assert!(db.with_connection(|conn| archived_columns(conn, &board_id)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_create_needs_a_live_board() {
+        let (db, _temp) = create_test_db();
+        let now = Utc::now().to_rfc3339();
+        let board = db.with_connection(|conn| seed_board(conn, "Board")).unwrap();
+        let input = |board_id: &str| CreateColumnInput {
+            board_id: board_id.to_string(),
+            name: "To Do".to_string(),
+            order: None,
+        };
+
+        let column = db.with_transaction(|tx| create(tx, &input(&board.id), &now)).unwrap();
+        assert_eq!(column.order, 1.0);
+
+        let err = db.with_transaction(|tx| create(tx, &input("missing"), &now)).unwrap_err();
+        assert!(matches!(err, DbError::NotFound { kind: "board", .. }));
+        assert_eq!(AppError::from(err).code(), "notFound");
+
+        db.with_connection(|conn| set_deleted_at(conn, TrashKind::Board, &board.id, Some(&now))).unwrap();
+        assert!(matches!(
+            db.with_transaction(|tx| create(tx, &input(&board.id), &now)),
+            Err(DbError::NotFound { kind: "board", .. })
+        ));
+        let count: i64 = db
+            .with_connection(|conn| conn.query_row("SELECT COUNT(*) FROM columns", [], |row| row.get(0)))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_live_column_board_skips_trashed_columns() {
+        let (db, _temp) = create_test_db();
+
+        let (board_id, col_id) = db.with_connection(|conn| {
+            let board = seed_board(conn, "Board")?;
+            let column = seed_column(conn, &board.id, "Column", 1.0)?;
+            Ok((board.id, column.id))
+        }).unwrap();
+
+        let found = db.with_connection(|conn| live_column_board(conn, &col_id)).unwrap();
+        assert_eq!(found, Some(board_id));
+        assert!(db.with_connection(|conn| live_column_board(conn, "missing")).unwrap().is_none());
+
+        db.with_connection(|conn| conn.execute("UPDATE columns SET archived = 1 WHERE id = ?", [&col_id])).unwrap();
+        assert!(db.with_connection(|conn| live_column_board(conn, &col_id)).unwrap().is_none());
+
+        db.with_connection(|conn| {
+            conn.execute("UPDATE columns SET archived = 0, deleted_at = '2026-01-01T00:00:00Z' WHERE id = ?", [&col_id])
+        }).unwrap();
+        assert!(db.with_connection(|conn| live_column_board(conn, &col_id)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_wip_limit_policies() {
+        let (db, _temp) = create_test_db();
+
+        let col_id = db.with_connection(|conn| {
+            let board = seed_board(conn, "Board")?;
+            let doing = Column { wip_limit: Some(2), ..column(&board.id, "Doing", 1.0) };
+            insert_column(conn, &doing)?;
+
+            // Archived cards don't count towards the limit
+            for (title, archived) in [("One", false), ("Two", false), ("Old", true)] {
+                insert_card(conn, &Card { archived, ..card(&doing.id, title, 1.0) })?;
+            }
+
+            Ok(doing.id)
+        }).unwrap();
+
+        assert!(db.with_transaction(|tx| check_wip_limit(tx, &col_id)).unwrap().is_none());
+
+        db.with_connection(|conn| seed_card(conn, &col_id, "Three", 3.0)).unwrap();
+
+        let exceeded = db.with_transaction(|tx| check_wip_limit(tx, &col_id)).unwrap().unwrap();
+        assert_eq!((exceeded.limit, exceeded.count), (2, 3));
+        assert_eq!(exceeded.column_name, "Doing");
+
+        db.with_connection(|conn| conn.execute("UPDATE columns SET wip_policy = 'block' WHERE id = ?", [&col_id])).unwrap();
+        let column = db.with_connection(|conn| fetch_column(conn, &col_id)).unwrap().unwrap();
+        assert_eq!(column.wip_policy, WipPolicy::Block);
+
+        let err = db.with_transaction(|tx| check_wip_limit(tx, &col_id)).unwrap_err();
+        assert!(matches!(err, DbError::WipLimit { limit: 2, .. }));
+        assert_eq!(AppError::from(err).code(), "wipLimit");
+    }
+
+    #[test]
+    fn test_blocking_limit_covers_cards_coming_back() {
+        let (db, _temp) = create_test_db();
+        let now = Utc::now().to_rfc3339();
+
+        let (board_id, todo_id, doing_id, ids) = db.with_connection(|conn| {
+            let board = seed_board(conn, "Board")?;
+            let todo = seed_column(conn, &board.id, "To Do", 1.0)?;
+            let doing = Column { wip_limit: Some(1), wip_policy: WipPolicy::Block, ..column(&board.id, "Doing", 2.0) };
+            insert_column(conn, &doing)?;
+
+            let one = seed_card(conn, &doing.id, "One", 1.0)?;
+            let old = Card { archived: true, ..card(&doing.id, "Old", 1.0) };
+            insert_card(conn, &old)?;
+            let trashed = seed_card(conn, &doing.id, "Trashed", 1.0)?;
+            set_deleted_at(conn, TrashKind::Card, &trashed.id, Some(&now))?;
+
+            Ok((board.id, todo.id, doing.id, vec![one.id, old.id, trashed.id]))
+        }).unwrap();
+
+        let err = db.with_transaction(|tx| cards::unarchive(tx, &ids[1..2], &now)).unwrap_err();
+        assert!(matches!(err, DbError::WipLimit { limit: 1, .. }));
+        assert!(db.with_connection(|conn| fetch_card(conn, &ids[1])).unwrap().unwrap().archived);
+        let input: cards::UpdateCardInput = serde_json::from_str(r#"{"archived": false}"#).unwrap();
+        let err = db.with_transaction(|tx| cards::update(tx, &ids[1], &input, &now)).unwrap_err();
+        assert!(matches!(err, DbError::WipLimit { limit: 1, .. }));
+        assert!(db.with_connection(|conn| fetch_card(conn, &ids[1])).unwrap().unwrap().archived);
+
+        let err = db.with_transaction(|tx| restore(tx, TrashKind::Card, &ids[2])).unwrap_err();
+        assert!(matches!(err, DbError::WipLimit { limit: 1, .. }));
+
+        // Undoing a move out of the column is refused once something else took the slot
+        db.with_transaction(|tx| move_to(tx, &ids[0], &todo_id, None, 1.0, &now)).unwrap();
+        db.with_connection(|conn| seed_card(conn, &doing_id, "Two", 2.0)).unwrap();
+        let err = db.with_transaction(|tx| undo_last(tx, &board_id)).unwrap_err();
+        assert!(matches!(err, DbError::WipLimit { limit: 1, .. }));
+        assert_eq!(db.with_connection(|conn| fetch_card(conn, &ids[0])).unwrap().unwrap().column_id, todo_id);
+    }
 }
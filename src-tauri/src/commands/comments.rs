@@ -0,0 +1,209 @@
+use crate::commands::cards::fetch_card;
+use crate::db::{Database, DbError};
+use crate::error::AppError;
+use crate::validation::{Limits, Validate};
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    pub id: String,
+    pub card_id: String,
+    pub body: String,
+    pub created_at: String,
+    /// Set the first time the body is changed and on every edit after that.
+    pub edited_at: Option<String>,
+}
+
+/// Column list matching `Comment::from_row`.
+pub(crate) const COMMENT_COLUMNS: &str = "id, card_id, body, created_at, edited_at";
+
+impl Comment {
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Comment {
+            id: row.get(0)?,
+            card_id: row.get(1)?,
+            body: row.get(2)?,
+            created_at: row.get(3)?,
+            edited_at: row.get(4)?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCommentInput {
+    pub card_id: String,
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCommentInput {
+    pub body: String,
+}
+
+fn fetch_comment(conn: &Connection, id: &str) -> rusqlite::Result<Option<Comment>> {
+    conn.query_row(
+        &format!("SELECT {COMMENT_COLUMNS} FROM card_comments WHERE id = ?"),
+        [id],
+        Comment::from_row,
+    )
+    .optional()
+}
+
+/// A card's comment thread, oldest first.
+pub(crate) fn comments_for_card(conn: &Connection, card_id: &str) -> rusqlite::Result<Vec<Comment>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {COMMENT_COLUMNS} FROM card_comments WHERE card_id = ? ORDER BY created_at ASC, id ASC"
+    ))?;
+
+    let comments = stmt
+        .query_map([card_id], Comment::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(comments)
+}
+
+pub(crate) fn insert_comment(conn: &Connection, comment: &Comment) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO card_comments (id, card_id, body, created_at, edited_at) VALUES (?, ?, ?, ?, ?)",
+        rusqlite::params![
+            &comment.id,
+            &comment.card_id,
+            &comment.body,
+            &comment.created_at,
+            &comment.edited_at
+        ],
+    )?;
+    Ok(())
+}
+
+/// Adds a comment to the end of a card's thread.
+pub(crate) fn add(conn: &Connection, card_id: &str, body: &str, now: &str) -> Result<Comment, DbError> {
+    if fetch_card(conn, card_id)?.is_none() {
+        return Err(DbError::NotFound { kind: "card", id: card_id.to_string() });
+    }
+
+    let comment = Comment {
+        id: Uuid::new_v4().to_string(),
+        card_id: card_id.to_string(),
+        body: body.to_string(),
+        created_at: now.to_string(),
+        edited_at: None,
+    };
+    insert_comment(conn, &comment)?;
+    Ok(comment)
+}
+
+/// Replaces a comment's body. Returns `None` if the comment doesn't exist.
+pub(crate) fn edit_comment(conn: &Connection, id: &str, body: &str, now: &str) -> rusqlite::Result<Option<Comment>> {
+    conn.execute(
+        "UPDATE card_comments SET body = ?, edited_at = ? WHERE id = ?",
+        [body, now, id],
+    )?;
+    fetch_comment(conn, id)
+}
+
+#[tauri::command]
+pub fn get_comments(
+    db: tauri::State<'_, Arc<Database>>,
+    card_id: String,
+) -> Result<Vec<Comment>, AppError> {
+    db.with_connection(|conn| comments_for_card(conn, &card_id))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn add_comment(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    input: CreateCommentInput,
+) -> Result<Comment, AppError> {
+    input.validate(&limits)?;
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| add(tx, &input.card_id, &input.body, &now))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn update_comment(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    id: String,
+    input: UpdateCommentInput,
+) -> Result<Comment, AppError> {
+    input.validate(&limits)?;
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| {
+        edit_comment(tx, &id, &input.body, &now)?.ok_or_else(|| DbError::NotFound { kind: "comment", id: id.clone() })
+    })
+    .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn delete_comment(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), AppError> {
+    db.with_transaction(|tx| {
+        if tx.execute("DELETE FROM card_comments WHERE id = ?", [&id])? == 0 {
+            return Err(DbError::NotFound { kind: "comment", id: id.clone() });
+        }
+        Ok(())
+    })
+    .map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_helpers::test_helpers::{create_test_db, seed_board_with_card};
+
+    #[test]
+    fn test_comment_thread() {
+        let (db, _temp) = create_test_db();
+
+        let card_id = db.with_connection(|conn| {
+            let (_, _, card) = seed_board_with_card(conn)?;
+            Ok(card.id)
+        }).unwrap();
+
+        let first = Comment {
+            id: Uuid::new_v4().to_string(),
+            card_id: card_id.clone(),
+            body: "Blocked on review".to_string(),
+            created_at: "2026-03-01T09:00:00+00:00".to_string(),
+            edited_at: None,
+        };
+        let second = Comment {
+            id: Uuid::new_v4().to_string(),
+            body: "Review done, moving to Done".to_string(),
+            created_at: "2026-03-02T09:00:00+00:00".to_string(),
+            ..first.clone()
+        };
+        db.with_connection(|conn| {
+            insert_comment(conn, &second)?;
+            insert_comment(conn, &first)
+        }).unwrap();
+
+        let thread = db.with_connection(|conn| comments_for_card(conn, &card_id)).unwrap();
+        assert_eq!(thread.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(), vec![&first.id, &second.id]);
+
+        let now = Utc::now().to_rfc3339();
+        let edited = db.with_connection(|conn| edit_comment(conn, &first.id, "Unblocked", &now)).unwrap().unwrap();
+        assert_eq!(edited.body, "Unblocked");
+        assert_eq!(edited.created_at, first.created_at);
+        assert_eq!(edited.edited_at, Some(now.clone()));
+
+        assert!(db.with_connection(|conn| edit_comment(conn, "missing", "x", &now)).unwrap().is_none());
+
+        let added = db.with_transaction(|tx| add(tx, &card_id, "Shipped", &now)).unwrap();
+        assert_eq!(db.with_connection(|conn| comments_for_card(conn, &card_id)).unwrap().last().unwrap().id, added.id);
+        let err = db.with_transaction(|tx| add(tx, "missing", "Lost", &now)).unwrap_err();
+        assert!(matches!(err, DbError::NotFound { kind: "card", .. }));
+    }
+}
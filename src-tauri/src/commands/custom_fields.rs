@@ -0,0 +1,631 @@
+use crate::commands::cards::{fetch_card, normalize_timestamp};
+use crate::commands::columns::board_id_for_column;
+use crate::db::{Database, DbError};
+use crate::error::AppError;
+use crate::validation::{Limits, Validate};
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FieldKind {
+    Text,
+    Number,
+    /// An RFC 3339 timestamp, stored in UTC like card due dates.
+    Date,
+    SingleSelect,
+    MultiSelect,
+    Checkbox,
+}
+
+impl FieldKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            FieldKind::Text => "text",
+            FieldKind::Number => "number",
+            FieldKind::Date => "date",
+            FieldKind::SingleSelect => "singleSelect",
+            FieldKind::MultiSelect => "multiSelect",
+            FieldKind::Checkbox => "checkbox",
+        }
+    }
+
+    fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "text" => Some(FieldKind::Text),
+            "number" => Some(FieldKind::Number),
+            "date" => Some(FieldKind::Date),
+            "singleSelect" => Some(FieldKind::SingleSelect),
+            "multiSelect" => Some(FieldKind::MultiSelect),
+            "checkbox" => Some(FieldKind::Checkbox),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn has_options(self) -> bool {
+        matches!(self, FieldKind::SingleSelect | FieldKind::MultiSelect)
+    }
+}
+
+/// A field every card on a board can fill in.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomField {
+    pub id: String,
+    pub board_id: String,
+    pub name: String,
+    pub kind: FieldKind,
+    /// Choices for select fields, in display order. Empty for other kinds.
+    #[serde(default)]
+    pub options: Vec<String>,
+    pub order: f64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Column list matching `CustomField::from_row`.
+pub(crate) const FIELD_COLUMNS: &str = r#"id, board_id, name, kind, options, "order", created_at, updated_at"#;
+
+impl CustomField {
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let kind: String = row.get(3)?;
+        let options: String = row.get(4)?;
+
+        Ok(CustomField {
+            id: row.get(0)?,
+            board_id: row.get(1)?,
+            name: row.get(2)?,
+            kind: FieldKind::parse(&kind).ok_or_else(|| {
+                rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, kind.into())
+            })?,
+            options: serde_json::from_str(&options)
+                .map_err(|e| rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, e.into()))?,
+            order: row.get(5)?,
+            created_at: row.get(6)?,
+            updated_at: row.get(7)?,
+        })
+    }
+}
+
+/// One card's value for one field. The JSON type depends on the field's
+/// kind: a string, number, timestamp string, option string, array of option
+/// strings, or boolean.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CardFieldValue {
+    pub card_id: String,
+    pub field_id: String,
+    pub value: Value,
+    pub updated_at: String,
+}
+
+/// Column list matching `CardFieldValue::from_row`.
+pub(crate) const VALUE_COLUMNS: &str = "card_id, field_id, value, updated_at";
+
+impl CardFieldValue {
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let value: String = row.get(2)?;
+
+        Ok(CardFieldValue {
+            card_id: row.get(0)?,
+            field_id: row.get(1)?,
+            value: serde_json::from_str(&value)
+                .map_err(|e| rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, e.into()))?,
+            updated_at: row.get(3)?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefineFieldInput {
+    pub board_id: String,
+    pub name: String,
+    pub kind: FieldKind,
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFieldInput {
+    pub name: Option<String>,
+    /// Replaces the choices of a select field. Removed choices are taken out
+    /// of every card's value.
+    pub options: Option<Vec<String>>,
+    pub order: Option<f64>,
+}
+
+pub(crate) fn fetch_field(conn: &Connection, id: &str) -> rusqlite::Result<Option<CustomField>> {
+    conn.query_row(
+        &format!("SELECT {FIELD_COLUMNS} FROM custom_fields WHERE id = ?"),
+        [id],
+        CustomField::from_row,
+    )
+    .optional()
+}
+
+pub(crate) fn insert_field(conn: &Connection, field: &CustomField) -> rusqlite::Result<()> {
+    conn.execute(
+        r#"INSERT INTO custom_fields (id, board_id, name, kind, options, "order", created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+        rusqlite::params![
+            &field.id,
+            &field.board_id,
+            &field.name,
+            field.kind.as_str(),
+            serde_json::to_string(&field.options).unwrap_or_else(|_| "[]".to_string()),
+            field.order,
+            &field.created_at,
+            &field.updated_at
+        ],
+    )?;
+    Ok(())
+}
+
+/// A board's fields, in display order.
+pub(crate) fn fields_for_board(conn: &Connection, board_id: &str) -> rusqlite::Result<Vec<CustomField>> {
+    let mut stmt = conn.prepare(&format!(
+        r#"SELECT {FIELD_COLUMNS} FROM custom_fields WHERE board_id = ? ORDER BY "order" ASC"#
+    ))?;
+
+    let fields = stmt
+        .query_map([board_id], CustomField::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(fields)
+}
+
+pub(crate) fn values_for_card(conn: &Connection, card_id: &str) -> rusqlite::Result<Vec<CardFieldValue>> {
+    let mut stmt = conn.prepare(&format!(
+        r#"SELECT {VALUE_COLUMNS}
+           FROM card_field_values
+           WHERE card_id = ?
+           ORDER BY (SELECT "order" FROM custom_fields WHERE custom_fields.id = card_field_values.field_id) ASC"#
+    ))?;
+
+    let values = stmt
+        .query_map([card_id], CardFieldValue::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(values)
+}
+
+pub(crate) fn write_value(conn: &Connection, value: &CardFieldValue) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO card_field_values (card_id, field_id, value, updated_at) VALUES (?, ?, ?, ?)
+         ON CONFLICT(card_id, field_id) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        rusqlite::params![&value.card_id, &value.field_id, value.value.to_string(), &value.updated_at],
+    )?;
+    Ok(())
+}
+
+/// Checks a select field's choices.
+pub(crate) fn check_options(kind: FieldKind, options: &[String], limits: &Limits) -> Result<(), AppError> {
+    if !kind.has_options() {
+        if !options.is_empty() {
+            return Err(AppError::Validation("Only select fields have options".to_string()));
+        }
+        return Ok(());
+    }
+
+    if options.is_empty() {
+        return Err(AppError::Validation("A select field needs at least one option".to_string()));
+    }
+    for (i, option) in options.iter().enumerate() {
+        if option.trim().is_empty() || option.chars().count() > limits.max_name_len {
+            return Err(AppError::Validation(format!("Invalid option \"{}\"", option)));
+        }
+        if options[..i].contains(option) {
+            return Err(AppError::Validation(format!("Option \"{}\" is listed twice", option)));
+        }
+    }
+    Ok(())
+}
+
+/// Checks `value` against the field's kind and returns it in stored form.
+pub(crate) fn check_value(field: &CustomField, value: Value, limits: &Limits) -> Result<Value, AppError> {
+    let invalid = || AppError::Validation(format!("Invalid value for {} field \"{}\"", field.kind.as_str(), field.name));
+    let check_option = |option: &str| -> Result<(), AppError> {
+        if !field.options.iter().any(|o| o == option) {
+            return Err(AppError::Validation(format!(
+                "\"{}\" is not an option of field \"{}\"",
+                option, field.name
+            )));
+        }
+        Ok(())
+    };
+
+    match (field.kind, value) {
+        (FieldKind::Text, Value::String(text)) => {
+            if text.chars().count() > limits.max_title_len {
+                return Err(AppError::Validation(format!(
+                    "{} is longer than {} characters",
+                    field.name, limits.max_title_len
+                )));
+            }
+            Ok(Value::String(text))
+        }
+        (FieldKind::Number, Value::Number(number)) if number.as_f64().is_some_and(f64::is_finite) => {
+            Ok(Value::Number(number))
+        }
+        (FieldKind::Date, Value::String(date)) => Ok(Value::String(normalize_timestamp(&date)?)),
+        (FieldKind::SingleSelect, Value::String(option)) => {
+            check_option(&option)?;
+            Ok(Value::String(option))
+        }
+        (FieldKind::MultiSelect, Value::Array(items)) => {
+            let mut chosen: Vec<String> = Vec::new();
+            for item in items {
+                let Value::String(option) = item else {
+                    return Err(invalid());
+                };
+                check_option(&option)?;
+                if !chosen.contains(&option) {
+                    chosen.push(option);
+                }
+            }
+            Ok(Value::from(chosen))
+        }
+        (FieldKind::Checkbox, Value::Bool(checked)) => Ok(Value::Bool(checked)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Sets or, for a `null` value, clears a card's value for a field. The value
+/// must already have passed `check_value`.
+pub(crate) fn set_value(
+    conn: &Connection,
+    card_id: &str,
+    field_id: &str,
+    value: Value,
+    now: &str,
+) -> rusqlite::Result<Option<CardFieldValue>> {
+    if value.is_null() {
+        conn.execute(
+            "DELETE FROM card_field_values WHERE card_id = ? AND field_id = ?",
+            [card_id, field_id],
+        )?;
+        return Ok(None);
+    }
+
+    let value = CardFieldValue {
+        card_id: card_id.to_string(),
+        field_id: field_id.to_string(),
+        value,
+        updated_at: now.to_string(),
+    };
+    write_value(conn, &value)?;
+    Ok(Some(value))
+}
+
+/// Applies an update to a field. New options must already have passed
+/// `check_options`.
+pub(crate) fn update_field(
+    conn: &Connection,
+    id: &str,
+    input: &UpdateFieldInput,
+    now: &str,
+) -> Result<CustomField, DbError> {
+    let field = fetch_field(conn, id)?.ok_or_else(|| DbError::NotFound { kind: "field", id: id.to_string() })?;
+    let updated = CustomField {
+        name: input.name.clone().unwrap_or(field.name),
+        options: input.options.clone().unwrap_or(field.options),
+        order: input.order.unwrap_or(field.order),
+        updated_at: now.to_string(),
+        ..field
+    };
+
+    conn.execute(
+        r#"UPDATE custom_fields SET name = ?, options = ?, "order" = ?, updated_at = ? WHERE id = ?"#,
+        rusqlite::params![
+            &updated.name,
+            serde_json::to_string(&updated.options).unwrap_or_else(|_| "[]".to_string()),
+            updated.order,
+            &updated.updated_at,
+            id
+        ],
+    )?;
+    if input.options.is_some() && updated.kind.has_options() {
+        drop_removed_options(conn, &updated, now)?;
+    }
+    Ok(updated)
+}
+
+/// Takes choices that are no longer among a select field's options out of
+/// every card's value, clearing values left with nothing chosen.
+fn drop_removed_options(conn: &Connection, field: &CustomField, now: &str) -> rusqlite::Result<()> {
+    let values = conn
+        .prepare(&format!("SELECT {VALUE_COLUMNS} FROM card_field_values WHERE field_id = ?"))?
+        .query_map([&field.id], CardFieldValue::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let is_option = |value: &Value| value.as_str().is_some_and(|option| field.options.iter().any(|o| o == option));
+    for value in values {
+        let kept = match &value.value {
+            Value::Array(items) => {
+                let kept: Vec<Value> = items.iter().filter(|item| is_option(item)).cloned().collect();
+                if kept.len() == items.len() {
+                    continue;
+                }
+                (!kept.is_empty()).then_some(Value::Array(kept))
+            }
+            single if is_option(single) => continue,
+            _ => None,
+        };
+        set_value(conn, &value.card_id, &field.id, kept.unwrap_or(Value::Null), now)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_custom_fields(
+    db: tauri::State<'_, Arc<Database>>,
+    board_id: String,
+) -> Result<Vec<CustomField>, AppError> {
+    db.with_connection(|conn| fields_for_board(conn, &board_id))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn define_custom_field(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    input: DefineFieldInput,
+) -> Result<CustomField, AppError> {
+    input.validate(&limits)?;
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| {
+        let max_order: Option<f64> = tx.query_row(
+            r#"SELECT MAX("order") FROM custom_fields WHERE board_id = ?"#,
+            [&input.board_id],
+            |row| row.get(0),
+        )?;
+
+        let field = CustomField {
+            id: Uuid::new_v4().to_string(),
+            board_id: input.board_id.clone(),
+            name: input.name.clone(),
+            kind: input.kind,
+            options: input.options.clone(),
+            order: max_order.unwrap_or(0.0) + 1.0,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+        };
+        insert_field(tx, &field)?;
+        Ok(field)
+    })
+    .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn update_custom_field(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    id: String,
+    input: UpdateFieldInput,
+) -> Result<CustomField, AppError> {
+    input.validate(&limits)?;
+    if let Some(options) = &input.options {
+        // A field's kind never changes, so it can be checked up front
+        let field = db
+            .with_connection(|conn| fetch_field(conn, &id))?
+            .ok_or_else(|| AppError::NotFound(format!("Field {} not found", id)))?;
+        check_options(field.kind, options, &limits)?;
+    }
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| update_field(tx, &id, &input, &now))
+        .map_err(AppError::from)
+}
+
+/// Delete a field along with every card's value for it
+#[tauri::command]
+pub fn delete_custom_field(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), AppError> {
+    db.with_connection(|conn| {
+        conn.execute("DELETE FROM custom_fields WHERE id = ?", [&id])?;
+        Ok(())
+    })
+    .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn get_card_field_values(
+    db: tauri::State<'_, Arc<Database>>,
+    card_id: String,
+) -> Result<Vec<CardFieldValue>, AppError> {
+    db.with_connection(|conn| values_for_card(conn, &card_id))
+        .map_err(AppError::from)
+}
+
+/// Set a card's value for a field; `null` clears it. Returns the stored value,
+/// or `None` once cleared.
+#[tauri::command]
+pub fn set_card_field_value(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    card_id: String,
+    field_id: String,
+    value: Value,
+) -> Result<Option<CardFieldValue>, AppError> {
+    let (field, card) = db.with_connection(|conn| Ok((fetch_field(conn, &field_id)?, fetch_card(conn, &card_id)?)))?;
+    let field = field.ok_or_else(|| AppError::NotFound(format!("Field {} not found", field_id)))?;
+    let card = card.ok_or_else(|| AppError::NotFound(format!("Card {} not found", card_id)))?;
+    if db.with_connection(|conn| board_id_for_column(conn, &card.column_id))? != field.board_id {
+        return Err(AppError::Validation(format!("Field \"{}\" belongs to a different board", field.name)));
+    }
+
+    let value = if value.is_null() { value } else { check_value(&field, value, &limits)? };
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| Ok(set_value(tx, &card_id, &field_id, value, &now)?))
+        .map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_helpers::test_helpers::{create_test_db, seed_board_with_card};
+    use serde_json::json;
+
+    fn field(kind: FieldKind, options: &[&str]) -> CustomField {
+        CustomField {
+            id: Uuid::new_v4().to_string(),
+            board_id: "board".to_string(),
+            name: "Field".to_string(),
+            kind,
+            options: options.iter().map(|o| o.to_string()).collect(),
+            order: 1.0,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_values_are_checked_against_the_kind() {
+        let limits = Limits::default();
+
+        let text = field(FieldKind::Text, &[]);
+        assert_eq!(check_value(&text, json!("ACME"), &limits).unwrap(), json!("ACME"));
+        assert!(check_value(&text, json!(3), &limits).is_err());
+
+        let number = field(FieldKind::Number, &[]);
+        assert_eq!(check_value(&number, json!(2.5), &limits).unwrap(), json!(2.5));
+        assert!(check_value(&number, json!("2.5"), &limits).is_err());
+
+        let date = field(FieldKind::Date, &[]);
+        assert_eq!(
+            check_value(&date, json!("2026-03-01T10:00:00+02:00"), &limits).unwrap(),
+            json!("2026-03-01T08:00:00Z")
+        );
+        assert!(check_value(&date, json!("next week"), &limits).is_err());
+
+        let single = field(FieldKind::SingleSelect, &["staging", "production"]);
+        assert!(check_value(&single, json!("staging"), &limits).is_ok());
+        assert!(check_value(&single, json!("dev"), &limits).is_err());
+
+        let multi = field(FieldKind::MultiSelect, &["ios", "android", "web"]);
+        assert_eq!(
+            check_value(&multi, json!(["web", "ios", "web"]), &limits).unwrap(),
+            json!(["web", "ios"])
+        );
+        assert!(check_value(&multi, json!("web"), &limits).is_err());
+        assert!(check_value(&multi, json!(["web", 1]), &limits).is_err());
+
+        let checkbox = field(FieldKind::Checkbox, &[]);
+        assert_eq!(check_value(&checkbox, json!(true), &limits).unwrap(), json!(true));
+        assert!(check_value(&checkbox, json!("yes"), &limits).is_err());
+    }
+
+    #[test]
+    fn test_options_are_checked() {
+        let limits = Limits::default();
+
+        assert!(check_options(FieldKind::SingleSelect, &[], &limits).is_err());
+        assert!(check_options(FieldKind::MultiSelect, &["a".to_string(), "a".to_string()], &limits).is_err());
+        assert!(check_options(FieldKind::Text, &["a".to_string()], &limits).is_err());
+        assert!(check_options(FieldKind::SingleSelect, &["a".to_string(), "b".to_string()], &limits).is_ok());
+    }
+
+    fn seed(db: &Database) -> (String, String) {
+        db.with_connection(|conn| {
+            let (board, _, card) = seed_board_with_card(conn)?;
+            Ok((board.id, card.id))
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_set_and_clear_values() {
+        let (db, _temp) = create_test_db();
+        let now = Utc::now().to_rfc3339();
+
+        let (board_id, card_id) = seed(&db);
+
+        let customer = CustomField { board_id: board_id.clone(), name: "Customer".to_string(), ..field(FieldKind::Text, &[]) };
+        let env = CustomField {
+            board_id: board_id.clone(),
+            order: 0.0,
+            ..field(FieldKind::SingleSelect, &["staging", "production"])
+        };
+        db.with_connection(|conn| {
+            insert_field(conn, &customer)?;
+            insert_field(conn, &env)
+        }).unwrap();
+
+        let fields = db.with_connection(|conn| fields_for_board(conn, &board_id)).unwrap();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].options, vec!["staging", "production"]);
+
+        db.with_connection(|conn| set_value(conn, &card_id, &customer.id, json!("ACME"), &now)).unwrap();
+        db.with_connection(|conn| set_value(conn, &card_id, &env.id, json!("staging"), &now)).unwrap();
+        db.with_connection(|conn| set_value(conn, &card_id, &env.id, json!("production"), &now)).unwrap();
+
+        let values = db.with_connection(|conn| values_for_card(conn, &card_id)).unwrap();
+        assert_eq!(values.iter().map(|v| v.value.clone()).collect::<Vec<_>>(), vec![json!("production"), json!("ACME")]);
+
+        assert!(db.with_connection(|conn| set_value(conn, &card_id, &env.id, Value::Null, &now)).unwrap().is_none());
+        assert_eq!(db.with_connection(|conn| values_for_card(conn, &card_id)).unwrap().len(), 1);
+
+        // Values go with their field
+        db.with_connection(|conn| conn.execute("DELETE FROM custom_fields WHERE id = ?", [&customer.id])).unwrap();
+        assert!(db.with_connection(|conn| values_for_card(conn, &card_id)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_removed_options_leave_card_values() {
+        let (db, _temp) = create_test_db();
+        let now = Utc::now().to_rfc3339();
+        let (board_id, card_id) = seed(&db);
+        let single = CustomField {
+            id: "single".to_string(),
+            board_id: board_id.clone(),
+            name: "Env".to_string(),
+            kind: FieldKind::SingleSelect,
+            options: vec!["staging".to_string(), "production".to_string()],
+            order: 1.0,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+        };
+        let multi = CustomField {
+            id: "multi".to_string(),
+            name: "Teams".to_string(),
+            kind: FieldKind::MultiSelect,
+            options: vec!["web".to_string(), "ios".to_string(), "android".to_string()],
+            order: 2.0,
+            ..single.clone()
+        };
+        db.with_connection(|conn| {
+            insert_field(conn, &single)?;
+            insert_field(conn, &multi)?;
+            set_value(conn, &card_id, &single.id, json!("staging"), &now)?;
+            set_value(conn, &card_id, &multi.id, json!(["web", "ios"]), &now)
+        }).unwrap();
+
+        let drop = |id: &str, options: &[&str]| {
+            let input = UpdateFieldInput {
+                name: None,
+                options: Some(options.iter().map(|o| o.to_string()).collect()),
+                order: None,
+            };
+            db.with_transaction(|tx| update_field(tx, id, &input, &now)).unwrap();
+            db.with_connection(|conn| values_for_card(conn, &card_id)).unwrap()
+                .into_iter()
+                .map(|v| v.value)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(drop("multi", &["web", "android"]), vec![json!("staging"), json!(["web"])]);
+        assert_eq!(drop("single", &["production"]), vec![json!(["web"])]);
+        assert!(drop("multi", &["android"]).is_empty());
+
+        let missing = UpdateFieldInput { name: Some("Gone".to_string()), options: None, order: None };
+        assert!(matches!(
+            db.with_transaction(|tx| update_field(tx, "nope", &missing, &now)),
+            Err(DbError::NotFound { kind: "field", .. })
+        ));
+    }
+}
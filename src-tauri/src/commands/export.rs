@@ -0,0 +1,324 @@
+use crate::commands::attachments::{Attachment, AttachmentStore, ATTACHMENT_COLUMNS};
+use crate::commands::boards::{fetch_live_board, Board};
+use crate::commands::cards::{Card, CARD_COLUMNS};
+use crate::commands::checklists::{ChecklistItem, ITEM_COLUMNS};
+use crate::commands::columns::{Column, COLUMN_COLUMNS};
+use crate::commands::comments::{Comment, COMMENT_COLUMNS};
+use crate::commands::custom_fields::{CardFieldValue, CustomField, FIELD_COLUMNS, VALUE_COLUMNS};
+use crate::commands::labels::{Label, LABEL_COLUMNS};
+use crate::commands::lanes::{Lane, LANE_COLUMNS};
+use crate::commands::links::{CardLink, LINK_COLUMNS};
+use crate::db::Database;
+use crate::error::AppError;
+use chrono::Utc;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Identifies a file as a kanban board export.
+pub const EXPORT_FORMAT: &str = "kanban-board";
+
+/// Bumped whenever the document layout changes in a way older readers can't handle.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// A self-contained, versioned snapshot of one board. Archived columns and
+/// cards are included so an export round-trips without losing anything;
+/// trashed ones are left out.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardExport {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    /// Version of the app that produced the file, for diagnostics only.
+    pub app_version: String,
+    pub board: Board,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    pub columns: Vec<Column>,
+    #[serde(default)]
+    pub lanes: Vec<Lane>,
+    pub cards: Vec<Card>,
+    #[serde(default)]
+    pub checklist_items: Vec<ChecklistItem>,
+    #[serde(default)]
+    pub comments: Vec<Comment>,
+    #[serde(default)]
+    pub custom_fields: Vec<CustomField>,
+    #[serde(default)]
+    pub field_values: Vec<CardFieldValue>,
+    /// Links between the board's own cards; links to other boards are left out.
+    #[serde(default)]
+    pub links: Vec<CardLink>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    /// Attachment contents keyed by hash, so a file attached twice is stored once.
+    #[serde(default, with = "base64_files")]
+    pub files: BTreeMap<String, Vec<u8>>,
+}
+
+/// Writes file contents as base64 strings rather than arrays of numbers.
+mod base64_files {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::collections::BTreeMap;
+
+    pub fn serialize<S: Serializer>(files: &BTreeMap<String, Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(files.iter().map(|(hash, data)| (hash, STANDARD.encode(data))))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, Vec<u8>>, D::Error> {
+        BTreeMap::<String, String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(hash, data)| {
+                let data = STANDARD
+                    .decode(data)
+                    .map_err(|e| D::Error::custom(format!("file {}: {}", hash, e)))?;
+                Ok((hash, data))
+            })
+            .collect()
+    }
+}
+
+/// Reads everything belonging to a board. Returns `None` if the board doesn't
+/// exist or is in the trash, like `load_board`.
+pub(crate) fn build_export(conn: &Connection, board_id: &str) -> rusqlite::Result<Option<BoardExport>> {
+    let Some(board) = fetch_live_board(conn, board_id)? else {
+        return Ok(None);
+    };
+
+    let labels = conn
+        .prepare(&format!(
+            "SELECT {LABEL_COLUMNS} FROM labels WHERE board_id = ? ORDER BY created_at ASC"
+        ))?
+        .query_map([board_id], Label::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let columns = conn
+        .prepare(&format!(
+            r#"SELECT {COLUMN_COLUMNS} FROM columns WHERE board_id = ? AND deleted_at IS NULL ORDER BY "order" ASC"#
+        ))?
+        .query_map([board_id], Column::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let lanes = conn
+        .prepare(&format!(
+            r#"SELECT {LANE_COLUMNS} FROM lanes WHERE board_id = ? ORDER BY "order" ASC"#
+        ))?
+        .query_map([board_id], Lane::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let cards = conn
+        .prepare(&format!(
+            r#"SELECT {CARD_COLUMNS}
+               FROM cards
+               WHERE column_id IN (SELECT id FROM columns WHERE board_id = ? AND deleted_at IS NULL)
+                 AND deleted_at IS NULL
+               ORDER BY column_id, "order" ASC"#
+        ))?
+        .query_map([board_id], Card::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let checklist_items = conn
+        .prepare(&format!(
+            r#"SELECT {ITEM_COLUMNS}
+               FROM checklist_items
+               WHERE card_id IN (
+                   SELECT cards.id FROM cards JOIN columns ON columns.id = cards.column_id
+                   WHERE columns.board_id = ? AND columns.deleted_at IS NULL AND cards.deleted_at IS NULL
+               )
+               ORDER BY card_id, "order" ASC"#
+        ))?
+        .query_map([board_id], ChecklistItem::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let comments = conn
+        .prepare(&format!(
+            r#"SELECT {COMMENT_COLUMNS}
+               FROM card_comments
+               WHERE card_id IN (
+                   SELECT cards.id FROM cards JOIN columns ON columns.id = cards.column_id
+                   WHERE columns.board_id = ? AND columns.deleted_at IS NULL AND cards.deleted_at IS NULL
+               )
+               ORDER BY card_id, created_at ASC, id ASC"#
+        ))?
+        .query_map([board_id], Comment::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let custom_fields = conn
+        .prepare(&format!(
+            r#"SELECT {FIELD_COLUMNS} FROM custom_fields WHERE board_id = ? ORDER BY "order" ASC"#
+        ))?
+        .query_map([board_id], CustomField::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let field_values = conn
+        .prepare(&format!(
+            r#"SELECT {VALUE_COLUMNS}
+               FROM card_field_values
+               WHERE card_id IN (
+                   SELECT cards.id FROM cards JOIN columns ON columns.id = cards.column_id
+                   WHERE columns.board_id = ? AND columns.deleted_at IS NULL AND cards.deleted_at IS NULL
+               )
+               ORDER BY card_id, field_id"#
+        ))?
+        .query_map([board_id], CardFieldValue::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let links = conn
+        .prepare(&format!(
+            r#"WITH board_cards(id) AS (
+                   SELECT cards.id FROM cards JOIN columns ON columns.id = cards.column_id
+                   WHERE columns.board_id = ? AND columns.deleted_at IS NULL AND cards.deleted_at IS NULL
+               )
+               SELECT {LINK_COLUMNS}
+               FROM card_links
+               WHERE from_card_id IN board_cards AND to_card_id IN board_cards
+               ORDER BY created_at ASC"#
+        ))?
+        .query_map([board_id], CardLink::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let attachments = conn
+        .prepare(&format!(
+            r#"SELECT {ATTACHMENT_COLUMNS}
+               FROM card_attachments
+               WHERE card_id IN (
+                   SELECT cards.id FROM cards JOIN columns ON columns.id = cards.column_id
+                   WHERE columns.board_id = ? AND columns.deleted_at IS NULL AND cards.deleted_at IS NULL
+               )
+               ORDER BY card_id, created_at ASC"#
+        ))?
+        .query_map([board_id], Attachment::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Some(BoardExport {
+        format: EXPORT_FORMAT.to_string(),
+        version: EXPORT_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        board,
+        labels,
+        columns,
+        lanes,
+        cards,
+        checklist_items,
+        comments,
+        custom_fields,
+        field_values,
+        links,
+        attachments,
+        files: BTreeMap::new(),
+    }))
+}
+
+/// Reads the contents of every attachment in `export` from the store.
+/// Kept apart from `build_export` so the database lock isn't held for file I/O.
+pub(crate) fn read_files(export: &mut BoardExport, store: &AttachmentStore) -> std::io::Result<()> {
+    for attachment in &export.attachments {
+        if !export.files.contains_key(&attachment.hash) {
+            export.files.insert(attachment.hash.clone(), store.read(&attachment.hash)?);
+        }
+    }
+    Ok(())
+}
+
+/// Export a board with all its columns and cards to a JSON file
+#[tauri::command]
+pub fn export_board(
+    db: tauri::State<'_, Arc<Database>>,
+    store: tauri::State<'_, AttachmentStore>,
+    board_id: String,
+    path: String,
+) -> Result<(), AppError> {
+    let mut export = db
+        .with_connection(|conn| build_export(conn, &board_id))?
+        .ok_or_else(|| AppError::NotFound(format!("Board {} not found", board_id)))?;
+    read_files(&mut export, &store)?;
+
+    let json = serde_json::to_string_pretty(&export).map_err(|e| AppError::Io(e.to_string()))?;
+
+    // Write next to the destination first so a failed write never leaves a truncated file
+    let path = Path::new(&path);
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, json)?;
+    fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cards::insert_card;
+    use crate::commands::columns::insert_column;
+    use crate::commands::trash::{set_deleted_at, TrashKind};
+    use crate::db::test_helpers::test_helpers::{
+        card, column, create_test_db, seed_board, seed_board_with_card, seed_column,
+    };
+
+    #[test]
+    fn test_export_includes_archived_items() {
+        let (db, _temp) = create_test_db();
+
+        let board_id = db.with_connection(|conn| {
+            let board = seed_board(conn, "Roadmap")?;
+            let current = seed_column(conn, &board.id, "Now", 1.0)?;
+            insert_column(conn, &Column { archived: true, ..column(&board.id, "Old", 2.0) })?;
+            insert_card(conn, &Card { description: Some("details".to_string()), ..card(&current.id, "Live", 1.0) })?;
+            insert_card(conn, &Card { archived: true, ..card(&current.id, "Shelved", 2.0) })?;
+            Ok(board.id)
+        }).unwrap();
+
+        let export = db.with_connection(|conn| build_export(conn, &board_id)).unwrap().unwrap();
+
+        assert_eq!(export.format, EXPORT_FORMAT);
+        assert_eq!(export.version, EXPORT_VERSION);
+        assert_eq!(export.board.name, "Roadmap");
+        assert_eq!(export.columns.len(), 2);
+        assert!(export.columns[1].archived);
+        assert_eq!(export.cards.len(), 2);
+        assert!(export.cards.iter().any(|c| c.archived));
+    }
+
+    #[test]
+    fn test_export_missing_board() {
+        let (db, _temp) = create_test_db();
+
+        let export = db.with_connection(|conn| build_export(conn, "missing")).unwrap();
+        assert!(export.is_none());
+    }
+
+    #[test]
+    fn test_export_skips_trashed_board() {
+        let (db, _temp) = create_test_db();
+        let now = Utc::now().to_rfc3339();
+
+        let (board, _, _) = db.with_connection(seed_board_with_card).unwrap();
+        db.with_connection(|conn| set_deleted_at(conn, TrashKind::Board, &board.id, Some(&now))).unwrap();
+
+        let export = db.with_connection(|conn| build_export(conn, &board.id)).unwrap();
+        assert!(export.is_none());
+    }
+
+    #[test]
+    fn test_export_document_shape() {
+        let (db, _temp) = create_test_db();
+
+        let board_id = db.with_connection(|conn| Ok(seed_board(conn, "Empty")?.id)).unwrap();
+
+        let export = db.with_connection(|conn| build_export(conn, &board_id)).unwrap().unwrap();
+        let json: serde_json::Value = serde_json::to_value(&export).unwrap();
+
+        assert_eq!(json["format"], "kanban-board");
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["board"]["id"], board_id.as_str());
+        assert!(json["exportedAt"].is_string());
+        assert!(json["columns"].as_array().unwrap().is_empty());
+    }
+}
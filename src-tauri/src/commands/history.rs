@@ -0,0 +1,498 @@
+use crate::commands::boards::{upsert_board, Board};
+use crate::commands::cards::{upsert_card, Card};
+use crate::commands::columns::{check_wip_limit, upsert_column, Column};
+use crate::commands::trash::{deletion_timestamp, set_deleted_at, TrashKind};
+use crate::db::{Database, DbError};
+use crate::error::AppError;
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Each board keeps at most this many entries; its oldest are dropped when a
+/// new one is recorded.
+const MAX_ENTRIES: i64 = 500;
+
+const HISTORY_PAGE_SIZE: i64 = 50;
+
+/// A single idempotent write. Every journal entry stores the ops that redo it
+/// and the ops that undo it, both applied in order.
+///
+/// Nothing is ever hard-deleted by an op: undoing a create trashes the item,
+/// so checklists, comments and other rows that aren't journaled survive a
+/// later redo.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum Op {
+    PutBoard { board: Board },
+    PutColumn { column: Column },
+    PutCard { card: Card },
+    /// Moves an item into the trash, or restores it when `deleted` is false.
+    /// The trash time is taken when the op is applied, not when it was
+    /// journaled, so an undone create isn't purged as if trashed long ago.
+    SetDeleted { kind: TrashKind, id: String, deleted: bool },
+}
+
+impl Op {
+    fn apply(&self, conn: &Connection) -> rusqlite::Result<()> {
+        match self {
+            Op::PutBoard { board } => upsert_board(conn, board),
+            Op::PutColumn { column } => upsert_column(conn, column),
+            Op::PutCard { card } => upsert_card(conn, card),
+            Op::SetDeleted { kind, id, deleted } => {
+                set_deleted_at(conn, *kind, id, deleted.then(deletion_timestamp).as_deref()).map(|_| ())
+            }
+        }
+    }
+
+    /// Moves an item to the trash, e.g. to undo its creation.
+    pub(crate) fn trash(kind: TrashKind, id: &str) -> Op {
+        Op::SetDeleted { kind, id: id.to_string(), deleted: true }
+    }
+
+    /// Brings an item back out of the trash.
+    pub(crate) fn untrash(kind: TrashKind, id: &str) -> Op {
+        Op::SetDeleted { kind, id: id.to_string(), deleted: false }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub seq: i64,
+    pub board_id: String,
+    pub action: String,
+    pub summary: String,
+    pub undone: bool,
+    pub created_at: String,
+}
+
+const ENTRY_COLUMNS: &str = "seq, board_id, action, summary, undone, created_at";
+
+impl HistoryEntry {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(HistoryEntry {
+            seq: row.get(0)?,
+            board_id: row.get(1)?,
+            action: row.get(2)?,
+            summary: row.get(3)?,
+            undone: row.get::<_, i32>(4)? != 0,
+            created_at: row.get(5)?,
+        })
+    }
+}
+
+fn to_json(ops: &[Op]) -> rusqlite::Result<String> {
+    serde_json::to_string(ops).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+fn from_json(json: &str) -> rusqlite::Result<Vec<Op>> {
+    serde_json::from_str(json).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+/// Journals a change that has just been written on `conn`. Call it inside the
+/// same transaction as the change. Recording anything new discards the
+/// board's redo stack, as in any editor.
+pub(crate) fn record(
+    conn: &Connection,
+    board_id: &str,
+    action: &str,
+    summary: &str,
+    forward: &[Op],
+    inverse: &[Op],
+) -> rusqlite::Result<()> {
+    conn.execute("DELETE FROM history WHERE board_id = ? AND undone = 1", [board_id])?;
+
+    conn.execute(
+        "INSERT INTO history (board_id, action, summary, forward, inverse, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+        rusqlite::params![
+            board_id,
+            action,
+            summary,
+            to_json(forward)?,
+            to_json(inverse)?,
+            Utc::now().to_rfc3339()
+        ],
+    )?;
+
+    conn.execute(
+        "DELETE FROM history WHERE board_id = ?1
+           AND seq NOT IN (SELECT seq FROM history WHERE board_id = ?1 ORDER BY seq DESC LIMIT ?2)",
+        rusqlite::params![board_id, MAX_ENTRIES],
+    )?;
+    Ok(())
+}
+
+/// Reverts the board's most recent change that hasn't been undone yet.
+pub(crate) fn undo_last(conn: &Connection, board_id: &str) -> Result<Option<HistoryEntry>, DbError> {
+    step(
+        conn,
+        &format!(
+            "SELECT {ENTRY_COLUMNS}, inverse FROM history WHERE board_id = ? AND undone = 0 ORDER BY seq DESC LIMIT 1"
+        ),
+        board_id,
+        true,
+    )
+}
+
+/// Reapplies the board's earliest undone change.
+pub(crate) fn redo_next(conn: &Connection, board_id: &str) -> Result<Option<HistoryEntry>, DbError> {
+    step(
+        conn,
+        &format!(
+            "SELECT {ENTRY_COLUMNS}, forward FROM history WHERE board_id = ? AND undone = 1 ORDER BY seq ASC LIMIT 1"
+        ),
+        board_id,
+        false,
+    )
+}
+
+fn step(conn: &Connection, query: &str, board_id: &str, undone: bool) -> Result<Option<HistoryEntry>, DbError> {
+    loop {
+        let Some((mut entry, ops)) = conn
+            .query_row(query, [board_id], |row| Ok((HistoryEntry::from_row(row)?, row.get::<_, String>(6)?)))
+            .optional()?
+        else {
+            return Ok(None);
+        };
+
+        if apply_all(conn, &ops)? {
+            conn.execute(
+                "UPDATE history SET undone = ? WHERE seq = ?",
+                rusqlite::params![undone as i32, entry.seq],
+            )?;
+            entry.undone = undone;
+            return Ok(Some(entry));
+        }
+
+        // It will never apply, e.g. because its rows were purged from the
+        // trash. Drop it rather than leave it stuck on top of the stack.
+        conn.execute("DELETE FROM history WHERE seq = ?", [entry.seq])?;
+    }
+}
+
+/// Applies an entry's ops as a unit. Returns `false`, with nothing written,
+/// if they are unreadable or break a constraint. Cards the ops bring into a
+/// column count against its WIP limit as with any other write.
+fn apply_all(conn: &Connection, ops: &str) -> Result<bool, DbError> {
+    let Ok(ops) = from_json(ops) else {
+        return Ok(false);
+    };
+    let entering = entered_columns(conn, &ops)?;
+
+    conn.execute_batch("SAVEPOINT history_step")?;
+    let applied = ops.iter().try_for_each(|op| op.apply(conn)).map_err(DbError::from).and_then(|()| {
+        entering.iter().try_for_each(|column_id| check_wip_limit(conn, column_id).map(|_| ()))
+    });
+    match applied {
+        Ok(()) => {
+            conn.execute_batch("RELEASE history_step")?;
+            Ok(true)
+        }
+        Err(e) => {
+            conn.execute_batch("ROLLBACK TO history_step; RELEASE history_step")?;
+            match e {
+                DbError::Sqlite(rusqlite::Error::SqliteFailure(e, _))
+                    if e.code == rusqlite::ErrorCode::ConstraintViolation =>
+                {
+                    Ok(false)
+                }
+                e => Err(e),
+            }
+        }
+    }
+}
+
+/// Columns that ops are about to put a visible card into, judged against the
+/// state before they run.
+fn entered_columns(conn: &Connection, ops: &[Op]) -> rusqlite::Result<Vec<String>> {
+    let mut columns = Vec::new();
+    for op in ops {
+        let column_id = match op {
+            Op::PutCard { card } if !card.archived => {
+                let current: Option<(String, bool)> = conn
+                    .query_row(
+                        "SELECT column_id, archived FROM cards WHERE id = ? AND deleted_at IS NULL",
+                        [&card.id],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .optional()?;
+                (current != Some((card.column_id.clone(), false))).then(|| card.column_id.clone())
+            }
+            Op::SetDeleted { kind: TrashKind::Card, id, deleted: false } => conn
+                .query_row(
+                    "SELECT column_id FROM cards WHERE id = ? AND deleted_at IS NOT NULL",
+                    [id],
+                    |row| row.get(0),
+                )
+                .optional()?,
+            _ => None,
+        };
+        if let Some(column_id) = column_id.filter(|id| !columns.contains(id)) {
+            columns.push(column_id);
+        }
+    }
+    Ok(columns)
+}
+
+pub(crate) fn entries_for_board(conn: &Connection, board_id: &str) -> rusqlite::Result<Vec<HistoryEntry>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {ENTRY_COLUMNS} FROM history WHERE board_id = ? ORDER BY seq DESC LIMIT ?"
+    ))?;
+
+    let entries = stmt
+        .query_map(rusqlite::params![board_id, HISTORY_PAGE_SIZE], HistoryEntry::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(entries)
+}
+
+/// Undo the most recent change to a board. Returns the entry that was
+/// reverted, or `None` if there is nothing left to undo.
+#[tauri::command]
+pub fn undo(db: tauri::State<'_, Arc<Database>>, board_id: String) -> Result<Option<HistoryEntry>, AppError> {
+    db.with_transaction(|tx| undo_last(tx, &board_id)).map_err(AppError::from)
+}
+
+/// Redo the most recently undone change to a board.
+#[tauri::command]
+pub fn redo(db: tauri::State<'_, Arc<Database>>, board_id: String) -> Result<Option<HistoryEntry>, AppError> {
+    db.with_transaction(|tx| redo_next(tx, &board_id)).map_err(AppError::from)
+}
+
+/// Recent changes to a board, newest first, including undone ones that can still be redone.
+#[tauri::command]
+pub fn get_history(
+    db: tauri::State<'_, Arc<Database>>,
+    board_id: String,
+) -> Result<Vec<HistoryEntry>, AppError> {
+    db.with_connection(|conn| entries_for_board(conn, &board_id))
+        .map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cards::fetch_card;
+    use crate::commands::checklists::{add_item, items_for_card};
+    use crate::commands::columns::live_column_board;
+    use crate::commands::comments::{comments_for_card, insert_comment, Comment};
+    use crate::commands::trash::{list, purge, retention_cutoff};
+    use crate::db::test_helpers::test_helpers::{create_test_db, seed_board, seed_card, seed_column};
+    use uuid::Uuid;
+
+    fn seed(conn: &Connection) -> rusqlite::Result<(Column, Card)> {
+        let board = seed_board(conn, "Board")?;
+        let column = seed_column(conn, &board.id, "To Do", 1.0)?;
+        let card = seed_card(conn, &column.id, "Task", 1.0)?;
+        Ok((column, card))
+    }
+
+    #[test]
+    fn test_undo_and_redo_update() {
+        let (db, _temp) = create_test_db();
+
+        let (column, before) = db.with_connection(seed).unwrap();
+        let mut after = before.clone();
+        after.title = "Renamed".to_string();
+
+        db.with_connection(|conn| {
+            upsert_card(conn, &after)?;
+            record(
+                conn,
+                &column.board_id,
+                "update_card",
+                "Rename card",
+                &[Op::PutCard { card: after.clone() }],
+                &[Op::PutCard { card: before.clone() }],
+            )
+        }).unwrap();
+
+        let undone = db.with_transaction(|tx| undo_last(tx, &column.board_id)).unwrap().unwrap();
+        assert!(undone.undone);
+        assert_eq!(undone.action, "update_card");
+        let card = db.with_connection(|conn| fetch_card(conn, &before.id)).unwrap().unwrap();
+        assert_eq!(card.title, "Task");
+
+        assert!(db.with_transaction(|tx| undo_last(tx, &column.board_id)).unwrap().is_none());
+
+        db.with_transaction(|tx| redo_next(tx, &column.board_id)).unwrap().unwrap();
+        let card = db.with_connection(|conn| fetch_card(conn, &before.id)).unwrap().unwrap();
+        assert_eq!(card.title, "Renamed");
+
+        assert!(db.with_transaction(|tx| redo_next(tx, &column.board_id)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_undo_create_then_redo() {
+        let (db, _temp) = create_test_db();
+
+        let (column, card) = db.with_connection(seed).unwrap();
+
+        db.with_connection(|conn| {
+            record(
+                conn,
+                &column.board_id,
+                "create_column",
+                "Add column",
+                &[
+                    Op::PutColumn { column: column.clone() },
+                    Op::PutCard { card: card.clone() },
+                    Op::untrash(TrashKind::Column, &column.id),
+                ],
+                &[Op::trash(TrashKind::Column, &column.id)],
+            )
+        }).unwrap();
+
+        // Children that aren't journaled themselves
+        let now = Utc::now().to_rfc3339();
+        db.with_connection(|conn| add_item(conn, &card.id, "Write tests", &now)).unwrap();
+        db.with_connection(|conn| {
+            insert_comment(conn, &Comment {
+                id: Uuid::new_v4().to_string(),
+                card_id: card.id.clone(),
+                body: "Looks good".to_string(),
+                created_at: now.clone(),
+                edited_at: None,
+            })
+        }).unwrap();
+
+        db.with_transaction(|tx| undo_last(tx, &column.board_id)).unwrap();
+        assert!(db.with_connection(|conn| live_column_board(conn, &column.id)).unwrap().is_none());
+        assert!(db.with_connection(|conn| fetch_card(conn, &card.id)).unwrap().is_some());
+
+        db.with_transaction(|tx| redo_next(tx, &column.board_id)).unwrap();
+        assert!(db.with_connection(|conn| live_column_board(conn, &column.id)).unwrap().is_some());
+        let restored = db.with_connection(|conn| fetch_card(conn, &card.id)).unwrap().unwrap();
+        assert_eq!(restored.column_id, column.id);
+        assert_eq!(db.with_connection(|conn| items_for_card(conn, &card.id)).unwrap().len(), 1);
+        assert_eq!(db.with_connection(|conn| comments_for_card(conn, &card.id)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_new_change_clears_redo_stack() {
+        let (db, _temp) = create_test_db();
+
+        let (column, card) = db.with_connection(seed).unwrap();
+        let ops = [Op::PutCard { card: card.clone() }];
+
+        db.with_transaction(|tx| {
+            record(tx, &column.board_id, "update_card", "First", &ops, &ops)?;
+            undo_last(tx, &column.board_id)?;
+            Ok(record(tx, &column.board_id, "update_card", "Second", &ops, &ops)?)
+        }).unwrap();
+
+        let history = db.with_connection(|conn| entries_for_board(conn, &column.board_id)).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].summary, "Second");
+        assert!(db.with_transaction(|tx| redo_next(tx, &column.board_id)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_undo_only_touches_its_board() {
+        let (db, _temp) = create_test_db();
+
+        let (first, first_card) = db.with_connection(seed).unwrap();
+        let (second, _) = db.with_connection(seed).unwrap();
+        let mut renamed = first_card.clone();
+        renamed.title = "Renamed".to_string();
+
+        db.with_connection(|conn| {
+            upsert_card(conn, &renamed)?;
+            record(
+                conn,
+                &first.board_id,
+                "update_card",
+                "Rename card",
+                &[Op::PutCard { card: renamed.clone() }],
+                &[Op::PutCard { card: first_card.clone() }],
+            )
+        }).unwrap();
+
+        assert!(db.with_transaction(|tx| undo_last(tx, &second.board_id)).unwrap().is_none());
+        let card = db.with_connection(|conn| fetch_card(conn, &first_card.id)).unwrap().unwrap();
+        assert_eq!(card.title, "Renamed");
+
+        db.with_transaction(|tx| undo_last(tx, &first.board_id)).unwrap().unwrap();
+        let card = db.with_connection(|conn| fetch_card(conn, &first_card.id)).unwrap().unwrap();
+        assert_eq!(card.title, "Task");
+
+        // A change on another board keeps this board's redo stack
+        let ops = [Op::PutCard { card: renamed.clone() }];
+        db.with_connection(|conn| record(conn, &second.board_id, "update_card", "Other", &ops, &ops)).unwrap();
+        assert!(db.with_transaction(|tx| redo_next(tx, &first.board_id)).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_entries_that_cannot_apply_are_dropped() {
+        let (db, _temp) = create_test_db();
+
+        let (column, card) = db.with_connection(seed).unwrap();
+        let mut renamed = card.clone();
+        renamed.title = "Renamed".to_string();
+        let ops = [Op::PutCard { card: renamed.clone() }];
+
+        db.with_connection(|conn| {
+            record(conn, &column.board_id, "update_card", "Rename card", &ops, &[Op::PutCard { card: card.clone() }])?;
+            conn.execute("DELETE FROM columns WHERE id = ?", [&column.id])?;
+            record(conn, &column.board_id, "update_card", "Rename again", &ops, &ops)
+        }).unwrap();
+
+        // Both entries point at the deleted column, so neither can be undone
+        assert!(db.with_transaction(|tx| undo_last(tx, &column.board_id)).unwrap().is_none());
+        assert!(db.with_connection(|conn| entries_for_board(conn, &column.board_id)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_undone_create_is_trashed_at_undo_time() {
+        let (db, _temp) = create_test_db();
+
+        let (column, card) = db.with_connection(seed).unwrap();
+        db.with_connection(|conn| {
+            record(
+                conn,
+                &column.board_id,
+                "create_card",
+                "Add card",
+                &[Op::PutCard { card: card.clone() }, Op::untrash(TrashKind::Card, &card.id)],
+                &[Op::trash(TrashKind::Card, &card.id)],
+            )?;
+            conn.execute("UPDATE history SET created_at = '2020-01-01T00:00:00Z'", [])
+        }).unwrap();
+
+        db.with_transaction(|tx| undo_last(tx, &column.board_id)).unwrap().unwrap();
+        db.with_transaction(|tx| Ok(purge(tx, Some(&retention_cutoff()))?)).unwrap();
+
+        let trashed = db.with_connection(|conn| list(conn, Some(&column.board_id))).unwrap();
+        assert_eq!(trashed.iter().map(|item| item.id.as_str()).collect::<Vec<_>>(), vec![card.id.as_str()]);
+        assert!(db.with_transaction(|tx| redo_next(tx, &column.board_id)).unwrap().is_some());
+        assert!(db.with_connection(|conn| fetch_card(conn, &card.id)).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_history_is_trimmed_per_board() {
+        let (db, _temp) = create_test_db();
+
+        let (quiet, card) = db.with_connection(seed).unwrap();
+        let (busy, _) = db.with_connection(seed).unwrap();
+        let ops = [Op::PutCard { card }];
+
+        db.with_connection(|conn| {
+            record(conn, &quiet.board_id, "update_card", "Quiet change", &ops, &ops)?;
+            for _ in 0..=MAX_ENTRIES {
+                record(conn, &busy.board_id, "update_card", "Busy change", &ops, &ops)?;
+            }
+            Ok(())
+        }).unwrap();
+
+        let count = |board_id: &str| {
+            db.with_connection(|conn| {
+                conn.query_row("SELECT COUNT(*) FROM history WHERE board_id = ?", [board_id], |row| row.get::<_, i64>(0))
+            }).unwrap()
+        };
+        assert_eq!(count(&quiet.board_id), 1);
+        assert_eq!(count(&busy.board_id), MAX_ENTRIES);
+    }
+}
@@ -0,0 +1,604 @@
+use crate::commands::attachments::{content_hash, insert_attachment, AttachmentStore};
+use crate::commands::boards::{insert_board, Board};
+use crate::commands::cards::insert_card;
+use crate::commands::checklists::insert_item;
+use crate::commands::columns::insert_column;
+use crate::commands::comments::insert_comment;
+use crate::commands::custom_fields::{insert_field, write_value};
+use crate::commands::export::{BoardExport, EXPORT_FORMAT, EXPORT_VERSION};
+use crate::commands::labels::insert_label;
+use crate::commands::lanes::insert_lane;
+use crate::commands::links::{insert_link, normalize, CardLink, LinkKind};
+use crate::db::Database;
+use crate::error::AppError;
+use crate::validation::{Limits, Validate};
+use chrono::Utc;
+use rusqlite::Connection;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportMode {
+    /// Insert the board as a new copy with freshly generated ids.
+    #[default]
+    Copy,
+    /// Keep the ids from the file, failing if any of them already exist.
+    Restore,
+}
+
+/// Parses an export document, rejecting files from other tools or newer app versions.
+pub(crate) fn parse_document(json: &str) -> Result<BoardExport, AppError> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| AppError::Validation(format!("Not a valid JSON file: {}", e)))?;
+
+    if value.get("format").and_then(|f| f.as_str()) != Some(EXPORT_FORMAT) {
+        return Err(AppError::Validation("File is not a kanban board export".to_string()));
+    }
+
+    let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+    if version == 0 || version > EXPORT_VERSION as u64 {
+        return Err(AppError::Validation(format!(
+            "Unsupported export version {} (this app reads up to version {})",
+            version, EXPORT_VERSION
+        )));
+    }
+
+    serde_json::from_value(value).map_err(|e| AppError::Validation(format!("Malformed board export: {}", e)))
+}
+
+/// Checks that every reference in the document points at something inside it.
+pub(crate) fn validate_document(doc: &BoardExport) -> Result<(), AppError> {
+    let mut seen = HashSet::new();
+    let ids = std::iter::once(&doc.board.id)
+        .chain(doc.labels.iter().map(|l| &l.id))
+        .chain(doc.columns.iter().map(|c| &c.id))
+        .chain(doc.lanes.iter().map(|l| &l.id))
+        .chain(doc.cards.iter().map(|c| &c.id))
+        .chain(doc.checklist_items.iter().map(|i| &i.id))
+        .chain(doc.comments.iter().map(|c| &c.id))
+        .chain(doc.custom_fields.iter().map(|f| &f.id))
+        .chain(doc.links.iter().map(|l| &l.id))
+        .chain(doc.attachments.iter().map(|a| &a.id));
+    for id in ids {
+        if !seen.insert(id) {
+            return Err(AppError::Validation(format!("Duplicate id {} in export", id)));
+        }
+    }
+
+    let label_ids: HashSet<_> = doc.labels.iter().map(|l| &l.id).collect();
+    let column_ids: HashSet<_> = doc.columns.iter().map(|c| &c.id).collect();
+    let lane_ids: HashSet<_> = doc.lanes.iter().map(|l| &l.id).collect();
+
+    if let Some(label) = doc.labels.iter().find(|l| l.board_id != doc.board.id) {
+        return Err(AppError::Validation(format!("Label {} belongs to a different board", label.id)));
+    }
+    if let Some(column) = doc.columns.iter().find(|c| c.board_id != doc.board.id) {
+        return Err(AppError::Validation(format!("Column {} belongs to a different board", column.id)));
+    }
+    if let Some(column) = doc.columns.iter().find(|c| !c.order.is_finite()) {
+        return Err(AppError::Validation(format!("Column {} has an invalid order", column.id)));
+    }
+    if let Some(lane) = doc.lanes.iter().find(|l| l.board_id != doc.board.id || !l.order.is_finite()) {
+        return Err(AppError::Validation(format!("Lane {} is invalid", lane.id)));
+    }
+
+    for card in &doc.cards {
+        if !column_ids.contains(&card.column_id) {
+            return Err(AppError::Validation(format!("Card {} references unknown column {}", card.id, card.column_id)));
+        }
+        if let Some(lane_id) = card.lane_id.as_ref().filter(|id| !lane_ids.contains(id)) {
+            return Err(AppError::Validation(format!("Card {} references unknown lane {}", card.id, lane_id)));
+        }
+        if !card.order.is_finite() {
+            return Err(AppError::Validation(format!("Card {} has an invalid order", card.id)));
+        }
+        if let Some(label_id) = card.label_ids.iter().find(|id| !label_ids.contains(id)) {
+            return Err(AppError::Validation(format!("Card {} references unknown label {}", card.id, label_id)));
+        }
+    }
+
+    if let Some(field) = doc
+        .custom_fields
+        .iter()
+        .find(|f| f.board_id != doc.board.id || !f.order.is_finite())
+    {
+        return Err(AppError::Validation(format!("Field {} is invalid", field.id)));
+    }
+
+    let card_ids: HashSet<_> = doc.cards.iter().map(|c| &c.id).collect();
+    for item in &doc.checklist_items {
+        if !card_ids.contains(&item.card_id) {
+            return Err(AppError::Validation(format!(
+                "Checklist item {} references unknown card {}",
+                item.id, item.card_id
+            )));
+        }
+        if !item.order.is_finite() {
+            return Err(AppError::Validation(format!("Checklist item {} has an invalid order", item.id)));
+        }
+    }
+    if let Some(comment) = doc.comments.iter().find(|c| !card_ids.contains(&c.card_id)) {
+        return Err(AppError::Validation(format!(
+            "Comment {} references unknown card {}",
+            comment.id, comment.card_id
+        )));
+    }
+    let field_ids: HashSet<_> = doc.custom_fields.iter().map(|f| &f.id).collect();
+    for value in &doc.field_values {
+        if !card_ids.contains(&value.card_id) || !field_ids.contains(&value.field_id) {
+            return Err(AppError::Validation(format!(
+                "Field value of card {} references an unknown card or field",
+                value.card_id
+            )));
+        }
+    }
+    let mut linked = HashSet::new();
+    for link in &doc.links {
+        if !card_ids.contains(&link.from_card_id) || !card_ids.contains(&link.to_card_id) {
+            return Err(AppError::Validation(format!("Link {} references an unknown card", link.id)));
+        }
+        // Links must already be in their stored form, see `links::normalize`
+        let stored = (link.from_card_id.as_str(), link.to_card_id.as_str(), link.kind);
+        if link.from_card_id == link.to_card_id || normalize(stored.0, stored.1, stored.2) != stored {
+            return Err(AppError::Validation(format!("Link {} is invalid", link.id)));
+        }
+        if !linked.insert(stored) {
+            return Err(AppError::Validation(format!("Link {} duplicates another link", link.id)));
+        }
+    }
+    if let Some(link) = blocking_cycle(&doc.links) {
+        return Err(AppError::Validation(format!("Link {} makes a card wait on itself", link.id)));
+    }
+    for attachment in &doc.attachments {
+        if !card_ids.contains(&attachment.card_id) {
+            return Err(AppError::Validation(format!(
+                "Attachment {} references unknown card {}",
+                attachment.id, attachment.card_id
+            )));
+        }
+        if !doc.files.contains_key(&attachment.hash) {
+            return Err(AppError::Validation(format!("Attachment {} has no file contents", attachment.id)));
+        }
+    }
+    if let Some(hash) = doc.files.iter().find(|(hash, data)| content_hash(data) != **hash).map(|(hash, _)| hash) {
+        return Err(AppError::Validation(format!("File {} doesn't match its hash", hash)));
+    }
+
+    Ok(())
+}
+
+/// A `blocks` link whose blocker also waits on the card it blocks, the same
+/// cycle `add_link` refuses.
+fn blocking_cycle(links: &[CardLink]) -> Option<&CardLink> {
+    let mut blocks: HashMap<&str, Vec<&str>> = HashMap::new();
+    for link in links.iter().filter(|l| l.kind == LinkKind::Blocks) {
+        blocks.entry(&link.from_card_id).or_default().push(&link.to_card_id);
+    }
+
+    links.iter().filter(|l| l.kind == LinkKind::Blocks).find(|link| {
+        let mut seen = HashSet::new();
+        let mut pending = vec![link.to_card_id.as_str()];
+        while let Some(id) = pending.pop() {
+            if id == link.from_card_id {
+                return true;
+            }
+            if seen.insert(id) {
+                pending.extend(blocks.get(id).into_iter().flatten());
+            }
+        }
+        false
+    })
+}
+
+/// Gives every entity in the document a fresh id, rewriting references to match.
+pub(crate) fn remap_ids(mut doc: BoardExport) -> BoardExport {
+    let mut ids: HashMap<String, String> = HashMap::new();
+    let mut fresh = |old: &str| -> String {
+        ids.entry(old.to_string())
+            .or_insert_with(|| Uuid::new_v4().to_string())
+            .clone()
+    };
+
+    doc.board.id = fresh(&doc.board.id);
+    for label in &mut doc.labels {
+        label.id = fresh(&label.id);
+        label.board_id = doc.board.id.clone();
+    }
+    for column in &mut doc.columns {
+        column.id = fresh(&column.id);
+        column.board_id = doc.board.id.clone();
+    }
+    for lane in &mut doc.lanes {
+        lane.id = fresh(&lane.id);
+        lane.board_id = doc.board.id.clone();
+    }
+    for card in &mut doc.cards {
+        card.id = fresh(&card.id);
+        card.column_id = fresh(&card.column_id);
+        card.lane_id = card.lane_id.as_deref().map(&mut fresh);
+        card.label_ids = card.label_ids.iter().map(|id| fresh(id)).collect();
+    }
+    for item in &mut doc.checklist_items {
+        item.id = fresh(&item.id);
+        item.card_id = fresh(&item.card_id);
+    }
+    for comment in &mut doc.comments {
+        comment.id = fresh(&comment.id);
+        comment.card_id = fresh(&comment.card_id);
+    }
+    for field in &mut doc.custom_fields {
+        field.id = fresh(&field.id);
+        field.board_id = doc.board.id.clone();
+    }
+    for value in &mut doc.field_values {
+        value.card_id = fresh(&value.card_id);
+        value.field_id = fresh(&value.field_id);
+    }
+    for link in &mut doc.links {
+        link.id = fresh(&link.id);
+        let (from, to) = (fresh(&link.from_card_id), fresh(&link.to_card_id));
+        // Fresh ids can flip which end of an undirected link sorts first
+        let (from, to, _) = normalize(&from, &to, link.kind);
+        (link.from_card_id, link.to_card_id) = (from.to_string(), to.to_string());
+    }
+    for attachment in &mut doc.attachments {
+        attachment.id = fresh(&attachment.id);
+        attachment.card_id = fresh(&attachment.card_id);
+    }
+
+    doc
+}
+
+/// Lists ids from the document that already exist in the database.
+pub(crate) fn find_conflicts(conn: &Connection, doc: &BoardExport) -> rusqlite::Result<Vec<String>> {
+    let mut conflicts = Vec::new();
+    let mut check = |table: &str, id: &str| -> rusqlite::Result<()> {
+        let exists: bool = conn.query_row(
+            &format!("SELECT EXISTS(SELECT 1 FROM {} WHERE id = ?)", table),
+            [id],
+            |row| row.get(0),
+        )?;
+        if exists {
+            conflicts.push(id.to_string());
+        }
+        Ok(())
+    };
+
+    check("boards", &doc.board.id)?;
+    for label in &doc.labels {
+        check("labels", &label.id)?;
+    }
+    for column in &doc.columns {
+        check("columns", &column.id)?;
+    }
+    for lane in &doc.lanes {
+        check("lanes", &lane.id)?;
+    }
+    for card in &doc.cards {
+        check("cards", &card.id)?;
+    }
+    for item in &doc.checklist_items {
+        check("checklist_items", &item.id)?;
+    }
+    for comment in &doc.comments {
+        check("card_comments", &comment.id)?;
+    }
+    for field in &doc.custom_fields {
+        check("custom_fields", &field.id)?;
+    }
+    for link in &doc.links {
+        check("card_links", &link.id)?;
+    }
+    for attachment in &doc.attachments {
+        check("card_attachments", &attachment.id)?;
+    }
+
+    Ok(conflicts)
+}
+
+/// Inserts a validated document. Run it inside a transaction so a failure
+/// part way through leaves nothing behind. Attachment files must already be
+/// in the store, see `store_files`.
+pub(crate) fn insert_document(conn: &Connection, doc: &BoardExport) -> rusqlite::Result<()> {
+    insert_board(conn, &doc.board)?;
+    for label in &doc.labels {
+        insert_label(conn, label)?;
+    }
+    for column in &doc.columns {
+        insert_column(conn, column)?;
+    }
+    for lane in &doc.lanes {
+        insert_lane(conn, lane)?;
+    }
+    for card in &doc.cards {
+        insert_card(conn, card)?;
+    }
+    for item in &doc.checklist_items {
+        insert_item(conn, item)?;
+    }
+    for comment in &doc.comments {
+        insert_comment(conn, comment)?;
+    }
+    for field in &doc.custom_fields {
+        insert_field(conn, field)?;
+    }
+    for value in &doc.field_values {
+        write_value(conn, value)?;
+    }
+    for link in &doc.links {
+        insert_link(conn, link)?;
+    }
+    for attachment in &doc.attachments {
+        insert_attachment(conn, attachment)?;
+    }
+
+    Ok(())
+}
+
+/// Copies the document's attachment files into the store. If the import then
+/// fails, the unused files are removed by the next startup's cleanup.
+pub(crate) fn store_files(doc: &BoardExport, store: &AttachmentStore) -> std::io::Result<()> {
+    for data in doc.files.values() {
+        store.put_bytes(data)?;
+    }
+    Ok(())
+}
+
+/// Import a board from a JSON export file
+#[tauri::command]
+pub fn import_board(
+    db: tauri::State<'_, Arc<Database>>,
+    store: tauri::State<'_, AttachmentStore>,
+    limits: tauri::State<'_, Limits>,
+    path: String,
+    mode: Option<ImportMode>,
+) -> Result<Board, AppError> {
+    let json = fs::read_to_string(&path)?;
+    let mut doc = parse_document(&json)?;
+    validate_document(&doc)?;
+    doc.validate(&limits)?;
+
+    let mode = mode.unwrap_or_default();
+    if mode == ImportMode::Copy {
+        doc = remap_ids(doc);
+        doc.board.last_opened_at = Some(Utc::now().to_rfc3339());
+    }
+
+    let conflicts = db
+        .with_connection(|conn| find_conflicts(conn, &doc))?;
+    if !conflicts.is_empty() {
+        return Err(AppError::Conflict(format!(
+            "Cannot restore board: {} item(s) already exist (first: {})",
+            conflicts.len(),
+            conflicts[0]
+        )));
+    }
+
+    store_files(&doc, &store)?;
+    db.with_transaction(|tx| Ok(insert_document(tx, &doc)?))?;
+
+    Ok(doc.board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::attachments::{attach, attachments_for_card};
+    use crate::commands::cards::Card;
+    use crate::commands::checklists::add_item;
+    use crate::commands::comments::Comment;
+    use crate::commands::export::{build_export, read_files};
+    use crate::commands::links::add_link;
+    use crate::db::DbError;
+    use crate::db::test_helpers::test_helpers::{card, create_test_db, seed_board, seed_card, seed_column, seed_label};
+
+    fn seed_sprint(conn: &Connection) -> rusqlite::Result<String> {
+        let board = seed_board(conn, "Sprint")?;
+        let column = seed_column(conn, &board.id, "To Do", 1.0)?;
+        let label = seed_label(conn, &board.id, "Bug")?;
+        let card = Card {
+            description: Some("notes".to_string()),
+            label_ids: vec![label.id],
+            ..card(&column.id, "Task", 1.0)
+        };
+        insert_card(conn, &card)?;
+        let card_id = card.id;
+        let now = Utc::now().to_rfc3339();
+
+        add_item(conn, &card_id, "Write tests", &now)?;
+        insert_comment(conn, &Comment {
+            id: Uuid::new_v4().to_string(),
+            card_id: card_id.clone(),
+            body: "Blocked on review".to_string(),
+            created_at: now.clone(),
+            edited_at: None,
+        })?;
+
+        Ok(board.id)
+    }
+
+    #[test]
+    fn test_copy_import_assigns_fresh_ids() {
+        let (db, _temp) = create_test_db();
+
+        let board_id = db.with_connection(seed_sprint).unwrap();
+        let export = db.with_connection(|conn| build_export(conn, &board_id)).unwrap().unwrap();
+        let json = serde_json::to_string(&export).unwrap();
+
+        let doc = remap_ids(parse_document(&json).unwrap());
+        validate_document(&doc).unwrap();
+        assert_ne!(doc.board.id, board_id);
+        assert_eq!(doc.cards[0].column_id, doc.columns[0].id);
+        assert_eq!(doc.cards[0].label_ids, vec![doc.labels[0].id.clone()]);
+        assert_eq!(doc.checklist_items[0].card_id, doc.cards[0].id);
+
+        db.with_connection(|conn| insert_document(conn, &doc)).unwrap();
+
+        let copy = db.with_connection(|conn| build_export(conn, &doc.board.id)).unwrap().unwrap();
+        assert_eq!(copy.board.name, "Sprint");
+        assert_eq!(copy.columns.len(), 1);
+        assert_eq!(copy.cards.len(), 1);
+        assert_eq!(copy.cards[0].description.as_deref(), Some("notes"));
+        assert_eq!(copy.cards[0].label_ids, vec![copy.labels[0].id.clone()]);
+        assert_eq!(copy.checklist_items.len(), 1);
+        assert_eq!(copy.checklist_items[0].text, "Write tests");
+        assert_eq!(copy.cards[0].checklist.total, 1);
+        assert_eq!(copy.comments.len(), 1);
+        assert_eq!(copy.comments[0].card_id, copy.cards[0].id);
+        assert_eq!(copy.comments[0].body, "Blocked on review");
+    }
+
+    #[test]
+    fn test_restore_detects_conflicts() {
+        let (db, _temp) = create_test_db();
+
+        let board_id = db.with_connection(seed_sprint).unwrap();
+        let export = db.with_connection(|conn| build_export(conn, &board_id)).unwrap().unwrap();
+
+        let conflicts = db.with_connection(|conn| find_conflicts(conn, &export)).unwrap();
+        assert_eq!(conflicts.len(), 6);
+        assert_eq!(conflicts[0], board_id);
+
+        db.with_connection(|conn| conn.execute("DELETE FROM boards WHERE id = ?", [&board_id])).unwrap();
+        let conflicts = db.with_connection(|conn| find_conflicts(conn, &export)).unwrap();
+        assert!(conflicts.is_empty());
+
+        db.with_connection(|conn| insert_document(conn, &export)).unwrap();
+        let restored = db.with_connection(|conn| build_export(conn, &board_id)).unwrap().unwrap();
+        assert_eq!(restored.cards[0].id, export.cards[0].id);
+    }
+
+    #[test]
+    fn test_failed_insert_rolls_back() {
+        let (db, _temp) = create_test_db();
+
+        let board_id = db.with_connection(seed_sprint).unwrap();
+        let mut export = db.with_connection(|conn| build_export(conn, &board_id)).unwrap().unwrap();
+
+        // Fresh board and column ids, but the card id collides with the existing card
+        export.board.id = Uuid::new_v4().to_string();
+        export.labels.clear();
+        export.cards[0].label_ids.clear();
+        export.columns[0].board_id = export.board.id.clone();
+        export.columns[0].id = Uuid::new_v4().to_string();
+        export.cards[0].column_id = export.columns[0].id.clone();
+
+        assert!(db.with_transaction(|tx| Ok(insert_document(tx, &export)?)).is_err());
+
+        let boards = db.with_connection(|conn| {
+            conn.query_row("SELECT COUNT(*) FROM boards", [], |row| row.get::<_, i32>(0))
+        }).unwrap();
+        assert_eq!(boards, 1);
+    }
+
+    #[test]
+    fn test_attachments_travel_with_the_export() {
+        let (db, _temp) = create_test_db();
+        let dir = tempfile::tempdir().unwrap();
+        let store = AttachmentStore::new(dir.path().join("attachments"));
+
+        let board_id = db.with_connection(seed_sprint).unwrap();
+        let mut export = db.with_connection(|conn| build_export(conn, &board_id)).unwrap().unwrap();
+        let source = dir.path().join("mockup.png");
+        fs::write(&source, b"not really a png").unwrap();
+        let now = Utc::now().to_rfc3339();
+        let stored = store.put_file(&source).unwrap();
+        db.with_transaction(|tx| attach(tx, &export.cards[0].id, &source, stored, &now)).unwrap();
+
+        export = db.with_connection(|conn| build_export(conn, &board_id)).unwrap().unwrap();
+        read_files(&mut export, &store).unwrap();
+        let json = serde_json::to_string(&export).unwrap();
+
+        // Imported into a different install with an empty store
+        let (other, _other_temp) = create_test_db();
+        let other_store = AttachmentStore::new(dir.path().join("other"));
+        let doc = remap_ids(parse_document(&json).unwrap());
+        validate_document(&doc).unwrap();
+        store_files(&doc, &other_store).unwrap();
+        other.with_transaction(|tx| Ok(insert_document(tx, &doc)?)).unwrap();
+
+        let attachments = other.with_connection(|conn| attachments_for_card(conn, &doc.cards[0].id)).unwrap();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].file_name, "mockup.png");
+        assert_eq!(other_store.read(&attachments[0].hash).unwrap(), b"not really a png");
+
+        let mut tampered = parse_document(&json).unwrap();
+        tampered.files.values_mut().for_each(|data| data.push(0));
+        assert!(validate_document(&tampered).unwrap_err().to_string().contains("doesn't match its hash"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_documents() {
+        assert!(parse_document("{").is_err());
+        assert!(parse_document(r#"{"format": "trello", "version": 1}"#).is_err());
+
+        let future = format!(r#"{{"format": "{}", "version": {}}}"#, EXPORT_FORMAT, EXPORT_VERSION + 1);
+        assert!(parse_document(&future).unwrap_err().to_string().contains("Unsupported export version"));
+
+        let (db, _temp) = create_test_db();
+        let board_id = db.with_connection(seed_sprint).unwrap();
+        let mut export = db.with_connection(|conn| build_export(conn, &board_id)).unwrap().unwrap();
+        export.cards[0].column_id = "nowhere".to_string();
+        assert!(validate_document(&export).unwrap_err().to_string().contains("unknown column"));
+
+        // Imported text is held to the same limits as text typed into the app
+        let limits = Limits::default();
+        let mut export = db.with_connection(|conn| build_export(conn, &board_id)).unwrap().unwrap();
+        assert!(export.validate(&limits).is_ok());
+        export.comments[0].body = "x".repeat(limits.max_description_len + 1);
+        assert!(matches!(export.validate(&limits), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_imported_links_keep_their_stored_form() {
+        let (db, _temp) = create_test_db();
+
+        let board_id = db.with_connection(seed_sprint).unwrap();
+        let now = Utc::now().to_rfc3339();
+        let (api, ui) = db.with_transaction(|tx| {
+            let column_id = build_export(tx, &board_id)?.unwrap().columns[0].id.clone();
+            let api = seed_card(tx, &column_id, "API", 2.0)?.id;
+            let ui = seed_card(tx, &column_id, "UI", 3.0)?.id;
+            add_link(tx, &api, &ui, LinkKind::RelatesTo, &now)?;
+            add_link(tx, &api, &ui, LinkKind::Blocks, &now)?;
+            Ok((api, ui))
+        }).unwrap();
+        let export = || db.with_connection(|conn| build_export(conn, &board_id)).unwrap().unwrap();
+
+        for _ in 0..16 {
+            let doc = remap_ids(export());
+            validate_document(&doc).unwrap();
+            let relates = doc.links.iter().find(|l| l.kind == LinkKind::RelatesTo).unwrap();
+            assert!(relates.from_card_id < relates.to_card_id);
+        }
+
+        let doc = remap_ids(export());
+        db.with_transaction(|tx| Ok(insert_document(tx, &doc)?)).unwrap();
+        let relates = doc.links.iter().find(|l| l.kind == LinkKind::RelatesTo).unwrap();
+        let err = db
+            .with_transaction(|tx| add_link(tx, &relates.to_card_id, &relates.from_card_id, LinkKind::RelatesTo, &now))
+            .unwrap_err();
+        assert!(matches!(err, DbError::DuplicateLink { .. }));
+
+        // A hand-edited file can't sneak in what `add_link` refuses
+        let mut cyclic = export();
+        cyclic.links.push(CardLink {
+            id: Uuid::new_v4().to_string(),
+            from_card_id: ui.clone(),
+            to_card_id: api.clone(),
+            kind: LinkKind::Blocks,
+            created_at: now.clone(),
+        });
+        assert!(validate_document(&cyclic).unwrap_err().to_string().contains("wait on itself"));
+
+        let mut duplicated = export();
+        let mut copy = duplicated.links[0].clone();
+        copy.id = Uuid::new_v4().to_string();
+        duplicated.links.push(copy);
+        assert!(validate_document(&duplicated).unwrap_err().to_string().contains("duplicates"));
+    }
+}
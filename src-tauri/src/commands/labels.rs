@@ -0,0 +1,285 @@
+use crate::db::Database;
+use crate::error::AppError;
+use crate::validation::{Limits, Validate};
+use chrono::Utc;
+use rusqlite::{Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Label {
+    pub id: String,
+    pub board_id: String,
+    pub name: String,
+    pub color: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Column list matching `Label::from_row`.
+pub(crate) const LABEL_COLUMNS: &str = "id, board_id, name, color, created_at, updated_at";
+
+impl Label {
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Label {
+            id: row.get(0)?,
+            board_id: row.get(1)?,
+            name: row.get(2)?,
+            color: row.get(3)?,
+            created_at: row.get(4)?,
+            updated_at: row.get(5)?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLabelInput {
+    pub board_id: String,
+    pub name: String,
+    pub color: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateLabelInput {
+    pub name: Option<String>,
+    pub color: Option<String>,
+}
+
+pub(crate) fn insert_label(conn: &Connection, label: &Label) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO labels (id, board_id, name, color, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
+        rusqlite::params![
+            &label.id,
+            &label.board_id,
+            &label.name,
+            &label.color,
+            &label.created_at,
+            &label.updated_at
+        ],
+    )?;
+    Ok(())
+}
+
+/// Links a label to a card. Returns `false` without writing anything when the
+/// label does not belong to the board the card lives on.
+pub(crate) fn link_label(conn: &Connection, card_id: &str, label_id: &str) -> rusqlite::Result<bool> {
+    let same_board: bool = conn.query_row(
+        r#"SELECT EXISTS(
+               SELECT 1 FROM cards c
+               INNER JOIN columns col ON c.column_id = col.id
+               INNER JOIN labels l ON l.board_id = col.board_id
+               WHERE c.id = ? AND l.id = ?
+           )"#,
+        [card_id, label_id],
+        |row| row.get(0),
+    )?;
+
+    if !same_board {
+        return Ok(false);
+    }
+
+    conn.execute(
+        "INSERT OR IGNORE INTO card_labels (card_id, label_id) VALUES (?, ?)",
+        [card_id, label_id],
+    )?;
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn get_labels_for_board(
+    db: tauri::State<'_, Arc<Database>>,
+    board_id: String,
+) -> Result<Vec<Label>, AppError> {
+    db.with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            r#"SELECT {LABEL_COLUMNS}
+               FROM labels
+               WHERE board_id = ?
+               ORDER BY name COLLATE NOCASE ASC"#
+        ))?;
+
+        let labels = stmt
+            .query_map([&board_id], Label::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(labels)
+    })
+    .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn create_label(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    input: CreateLabelInput,
+) -> Result<Label, AppError> {
+    input.validate(&limits)?;
+    let now = Utc::now().to_rfc3339();
+
+    let label = Label {
+        id: Uuid::new_v4().to_string(),
+        board_id: input.board_id,
+        name: input.name,
+        color: input.color,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+
+    db.with_connection(|conn| insert_label(conn, &label))?;
+
+    Ok(label)
+}
+
+#[tauri::command]
+pub fn update_label(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    id: String,
+    input: UpdateLabelInput,
+) -> Result<Label, AppError> {
+    input.validate(&limits)?;
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| {
+        tx.execute(
+            "UPDATE labels SET name = COALESCE(?, name), color = COALESCE(?, color), updated_at = ? WHERE id = ?",
+            rusqlite::params![&input.name, &input.color, &now, &id],
+        )?;
+
+        Ok(tx.query_row(
+            &format!("SELECT {LABEL_COLUMNS} FROM labels WHERE id = ?"),
+            [&id],
+            Label::from_row,
+        )?)
+    })
+    .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn delete_label(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), AppError> {
+    db.with_connection(|conn| {
+        conn.execute("DELETE FROM labels WHERE id = ?", [&id])?;
+        Ok(())
+    })
+    .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn attach_label(
+    db: tauri::State<'_, Arc<Database>>,
+    card_id: String,
+    label_id: String,
+) -> Result<(), AppError> {
+    let linked = db
+        .with_transaction(|tx| Ok(link_label(tx, &card_id, &label_id)?))?;
+
+    if !linked {
+        return Err(AppError::Validation("Label and card must belong to the same board".to_string()));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn detach_label(
+    db: tauri::State<'_, Arc<Database>>,
+    card_id: String,
+    label_id: String,
+) -> Result<(), AppError> {
+    db.with_connection(|conn| {
+        conn.execute(
+            "DELETE FROM card_labels WHERE card_id = ? AND label_id = ?",
+            [&card_id, &label_id],
+        )?;
+        Ok(())
+    })
+    .map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cards::{Card, CARD_COLUMNS};
+    use crate::db::test_helpers::test_helpers::{create_test_db, seed_board_with_card, seed_label};
+
+    #[test]
+    fn test_link_label_is_idempotent() {
+        let (db, _temp) = create_test_db();
+
+        let (card_id, label_id) = db.with_connection(|conn| {
+            let (board, _, card) = seed_board_with_card(conn)?;
+            let label_id = seed_label(conn, &board.id, "Bug")?.id;
+            Ok((card.id, label_id))
+        }).unwrap();
+
+        let linked = db.with_connection(|conn| {
+            link_label(conn, &card_id, &label_id)?;
+            link_label(conn, &card_id, &label_id)
+        }).unwrap();
+        assert!(linked);
+
+        let count = db.with_connection(|conn| {
+            conn.query_row("SELECT COUNT(*) FROM card_labels WHERE card_id = ?", [&card_id], |row| row.get::<_, i32>(0))
+        }).unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_link_label_rejects_other_board() {
+        let (db, _temp) = create_test_db();
+
+        let (card_id, foreign_label_id) = db.with_connection(|conn| {
+            let (_, _, card) = seed_board_with_card(conn)?;
+            let (other_board, _, _) = seed_board_with_card(conn)?;
+            let label_id = seed_label(conn, &other_board.id, "Bug")?.id;
+            Ok((card.id, label_id))
+        }).unwrap();
+
+        let linked = db.with_connection(|conn| link_label(conn, &card_id, &foreign_label_id)).unwrap();
+        assert!(!linked);
+    }
+
+    #[test]
+    fn test_card_carries_label_ids() {
+        let (db, _temp) = create_test_db();
+
+        let (card_id, bug_id, ui_id) = db.with_connection(|conn| {
+            let (board, _, card) = seed_board_with_card(conn)?;
+            let bug_id = seed_label(conn, &board.id, "Bug")?.id;
+            let ui_id = seed_label(conn, &board.id, "UI")?.id;
+            link_label(conn, &card.id, &bug_id)?;
+            link_label(conn, &card.id, &ui_id)?;
+            Ok((card.id, bug_id, ui_id))
+        }).unwrap();
+
+        let card = db.with_connection(|conn| {
+            conn.query_row(&format!("SELECT {CARD_COLUMNS} FROM cards WHERE id = ?"), [&card_id], Card::from_row)
+        }).unwrap();
+
+        assert_eq!(card.label_ids.len(), 2);
+        assert!(card.label_ids.contains(&bug_id));
+        assert!(card.label_ids.contains(&ui_id));
+    }
+
+    #[test]
+    fn test_delete_label_detaches_from_cards() {
+        let (db, _temp) = create_test_db();
+
+        let (card_id, label_id) = db.with_connection(|conn| {
+            let (board, _, card) = seed_board_with_card(conn)?;
+            let label_id = seed_label(conn, &board.id, "Bug")?.id;
+            link_label(conn, &card.id, &label_id)?;
+            Ok((card.id, label_id))
+        }).unwrap();
+
+        db.with_connection(|conn| conn.execute("DELETE FROM labels WHERE id = ?", [&label_id])).unwrap();
+
+        let card = db.with_connection(|conn| {
+            conn.query_row(&format!("SELECT {CARD_COLUMNS} FROM cards WHERE id = ?"), [&card_id], Card::from_row)
+        }).unwrap();
+        assert!(card.label_ids.is_empty());
+    }
+}
@@ -0,0 +1,301 @@
+use crate::db::{Database, DbError};
+use crate::error::AppError;
+use crate::validation::{Limits, Validate};
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A horizontal band across a board's columns, e.g. a class of service.
+/// Cards without a lane are shown after every lane.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Lane {
+    pub id: String,
+    pub board_id: String,
+    pub name: String,
+    pub order: f64,
+    /// Collapsed lanes are drawn as a header only.
+    pub collapsed: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Column list matching `Lane::from_row`.
+pub(crate) const LANE_COLUMNS: &str = r#"id, board_id, name, "order", collapsed, created_at, updated_at"#;
+
+impl Lane {
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Lane {
+            id: row.get(0)?,
+            board_id: row.get(1)?,
+            name: row.get(2)?,
+            order: row.get(3)?,
+            collapsed: row.get::<_, i32>(4)? != 0,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLaneInput {
+    pub board_id: String,
+    pub name: String,
+    pub order: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderLaneInput {
+    pub id: String,
+    pub order: f64,
+}
+
+pub(crate) fn fetch_lane(conn: &Connection, id: &str) -> rusqlite::Result<Option<Lane>> {
+    conn.query_row(
+        &format!("SELECT {LANE_COLUMNS} FROM lanes WHERE id = ?"),
+        [id],
+        Lane::from_row,
+    )
+    .optional()
+}
+
+pub(crate) fn insert_lane(conn: &Connection, lane: &Lane) -> rusqlite::Result<()> {
+    conn.execute(
+        r#"INSERT INTO lanes (id, board_id, name, "order", collapsed, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+        rusqlite::params![
+            &lane.id,
+            &lane.board_id,
+            &lane.name,
+            lane.order,
+            lane.collapsed as i32,
+            &lane.created_at,
+            &lane.updated_at
+        ],
+    )?;
+    Ok(())
+}
+
+/// A board's lanes, top to bottom.
+pub(crate) fn lanes_for_board(conn: &Connection, board_id: &str) -> rusqlite::Result<Vec<Lane>> {
+    let mut stmt = conn.prepare(&format!(
+        r#"SELECT {LANE_COLUMNS} FROM lanes WHERE board_id = ? ORDER BY "order" ASC"#
+    ))?;
+
+    let lanes = stmt
+        .query_map([board_id], Lane::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(lanes)
+}
+
+/// Adds a lane, at the bottom of the board unless an order is given.
+pub(crate) fn add_lane(
+    conn: &Connection,
+    board_id: &str,
+    name: &str,
+    order: Option<f64>,
+    now: &str,
+) -> rusqlite::Result<Lane> {
+    let order = match order {
+        Some(order) => order,
+        None => {
+            let max_order: Option<f64> = conn.query_row(
+                r#"SELECT MAX("order") FROM lanes WHERE board_id = ?"#,
+                [board_id],
+                |row| row.get(0),
+            )?;
+            max_order.unwrap_or(0.0) + 1.0
+        }
+    };
+
+    let lane = Lane {
+        id: Uuid::new_v4().to_string(),
+        board_id: board_id.to_string(),
+        name: name.to_string(),
+        order,
+        collapsed: false,
+        created_at: now.to_string(),
+        updated_at: now.to_string(),
+    };
+    insert_lane(conn, &lane)?;
+    Ok(lane)
+}
+
+/// Applies every order update or none of them, like `columns::reorder`.
+pub(crate) fn reorder(conn: &Connection, updates: &[ReorderLaneInput], now: &str) -> Result<(), DbError> {
+    for update in updates {
+        let changed = conn
+            .execute(
+                r#"UPDATE lanes SET "order" = ?, updated_at = ? WHERE id = ?"#,
+                rusqlite::params![update.order, now, &update.id],
+            )
+            .map_err(|source| DbError::ItemFailed { kind: "lane", id: update.id.clone(), source })?;
+        if changed == 0 {
+            return Err(DbError::NotFound { kind: "lane", id: update.id.clone() });
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_lanes_for_board(
+    db: tauri::State<'_, Arc<Database>>,
+    board_id: String,
+) -> Result<Vec<Lane>, AppError> {
+    db.with_connection(|conn| lanes_for_board(conn, &board_id))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn create_lane(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    input: CreateLaneInput,
+) -> Result<Lane, AppError> {
+    input.validate(&limits)?;
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| Ok(add_lane(tx, &input.board_id, &input.name, input.order, &now)?))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn reorder_lanes(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    updates: Vec<ReorderLaneInput>,
+) -> Result<(), AppError> {
+    updates.validate(&limits)?;
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| reorder(tx, &updates, &now))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn set_lane_collapsed(
+    db: tauri::State<'_, Arc<Database>>,
+    id: String,
+    collapsed: bool,
+) -> Result<Lane, AppError> {
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| {
+        tx.execute(
+            "UPDATE lanes SET collapsed = ?, updated_at = ? WHERE id = ?",
+            rusqlite::params![collapsed as i32, &now, &id],
+        )?;
+        fetch_lane(tx, &id)?.ok_or_else(|| DbError::NotFound { kind: "lane", id: id.clone() })
+    })
+    .map_err(AppError::from)
+}
+
+/// Delete a lane. Its cards stay on the board without a lane.
+#[tauri::command]
+pub fn delete_lane(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), AppError> {
+    db.with_connection(|conn| {
+        conn.execute("DELETE FROM lanes WHERE id = ?", [&id])?;
+        Ok(())
+    })
+    .map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cards::{cards_for_board, fetch_card, insert_card, move_to, Card};
+    use crate::db::test_helpers::test_helpers::{card, create_test_db, seed_board, seed_board_with_card, seed_column};
+
+    #[test]
+    fn test_cards_are_ordered_by_lane_then_column() {
+        let (db, _temp) = create_test_db();
+        let now = Utc::now().to_rfc3339();
+
+        let (board_id, todo_id, doing_id) = db.with_connection(|conn| {
+            let board = seed_board(conn, "Board")?;
+            let todo = seed_column(conn, &board.id, "To Do", 1.0)?;
+            let doing = seed_column(conn, &board.id, "Doing", 2.0)?;
+            Ok((board.id, todo.id, doing.id))
+        }).unwrap();
+
+        let normal = db.with_connection(|conn| add_lane(conn, &board_id, "Normal", None, &now)).unwrap();
+        let expedite = db.with_connection(|conn| add_lane(conn, &board_id, "Expedite", Some(0.0), &now)).unwrap();
+        assert_eq!(normal.order, 1.0);
+
+        db.with_connection(|conn| {
+            for (title, column_id, lane_id) in [
+                ("Unlaned", &todo_id, None),
+                ("Normal doing", &doing_id, Some(&normal.id)),
+                ("Normal todo", &todo_id, Some(&normal.id)),
+                ("Hotfix", &doing_id, Some(&expedite.id)),
+            ] {
+                insert_card(conn, &Card { lane_id: lane_id.cloned(), ..card(column_id, title, 1.0) })?;
+            }
+            Ok(())
+        }).unwrap();
+
+        let titles: Vec<_> = db
+            .with_connection(|conn| cards_for_board(conn, &board_id))
+            .unwrap()
+            .into_iter()
+            .map(|c| c.title)
+            .collect();
+        assert_eq!(titles, vec!["Hotfix", "Normal todo", "Normal doing", "Unlaned"]);
+
+        let lanes = db.with_connection(|conn| lanes_for_board(conn, &board_id)).unwrap();
+        assert_eq!(lanes.iter().map(|l| l.name.as_str()).collect::<Vec<_>>(), vec!["Expedite", "Normal"]);
+
+        // Deleting a lane leaves its cards on the board
+        let hotfix = db.with_connection(|conn| cards_for_board(conn, &board_id)).unwrap().remove(0);
+        db.with_connection(|conn| conn.execute("DELETE FROM lanes WHERE id = ?", [&expedite.id])).unwrap();
+        let hotfix = db.with_connection(|conn| fetch_card(conn, &hotfix.id)).unwrap().unwrap();
+        assert!(hotfix.lane_id.is_none());
+    }
+
+    #[test]
+    fn test_reorder_is_all_or_nothing() {
+        let (db, _temp) = create_test_db();
+        let now = Utc::now().to_rfc3339();
+
+        let board_id = db.with_connection(|conn| Ok(seed_board(conn, "Board")?.id)).unwrap();
+
+        let lane = db.with_connection(|conn| add_lane(conn, &board_id, "Maintenance", None, &now)).unwrap();
+        let updates = vec![
+            ReorderLaneInput { id: lane.id.clone(), order: 5.0 },
+            ReorderLaneInput { id: "missing".to_string(), order: 6.0 },
+        ];
+        let err = db.with_transaction(|tx| reorder(tx, &updates, &now)).unwrap_err();
+        assert!(matches!(err, DbError::NotFound { kind: "lane", .. }));
+        assert_eq!(db.with_connection(|conn| fetch_lane(conn, &lane.id)).unwrap().unwrap().order, 1.0);
+    }
+
+    #[test]
+    fn test_cards_only_join_lanes_on_their_board() {
+        let (db, _temp) = create_test_db();
+        let now = Utc::now().to_rfc3339();
+
+        let (board_id, other_board_id, col_id, card_id) = db.with_connection(|conn| {
+            let (board, column, card) = seed_board_with_card(conn)?;
+            let other_board = seed_board(conn, "Board")?;
+            Ok((board.id, other_board.id, column.id, card.id))
+        }).unwrap();
+
+        let lane = db.with_connection(|conn| add_lane(conn, &board_id, "Normal", None, &now)).unwrap();
+        let foreign = db.with_connection(|conn| add_lane(conn, &other_board_id, "Foreign", None, &now)).unwrap();
+
+        let err = db
+            .with_transaction(|tx| move_to(tx, &card_id, &col_id, Some(Some(&foreign.id)), 1.0, &now))
+            .unwrap_err();
+        assert!(matches!(err, DbError::WrongBoard { kind: "lane", .. }));
+        assert!(db.with_connection(|conn| fetch_card(conn, &card_id)).unwrap().unwrap().lane_id.is_none());
+
+        let moved = db
+            .with_transaction(|tx| move_to(tx, &card_id, &col_id, Some(Some(&lane.id)), 1.0, &now))
+            .unwrap();
+        assert_eq!(moved.card.lane_id, Some(lane.id));
+    }
+}
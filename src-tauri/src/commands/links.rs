@@ -0,0 +1,322 @@
+use crate::commands::cards::fetch_card;
+use crate::db::{Database, DbError};
+use crate::error::AppError;
+use crate::validation::{Limits, Validate};
+use chrono::Utc;
+use rusqlite::{Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum LinkKind {
+    /// The from card has to be finished before the to card.
+    Blocks,
+    /// Only accepted as input; stored as the reverse `Blocks`.
+    BlockedBy,
+    RelatesTo,
+    /// The from card is a duplicate of the to card.
+    Duplicates,
+    ParentOf,
+}
+
+impl LinkKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            LinkKind::Blocks => "blocks",
+            LinkKind::BlockedBy => "blockedBy",
+            LinkKind::RelatesTo => "relatesTo",
+            LinkKind::Duplicates => "duplicates",
+            LinkKind::ParentOf => "parentOf",
+        }
+    }
+
+    fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "blocks" => Some(LinkKind::Blocks),
+            "blockedBy" => Some(LinkKind::BlockedBy),
+            "relatesTo" => Some(LinkKind::RelatesTo),
+            "duplicates" => Some(LinkKind::Duplicates),
+            "parentOf" => Some(LinkKind::ParentOf),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CardLink {
+    pub id: String,
+    pub from_card_id: String,
+    pub to_card_id: String,
+    /// Never `BlockedBy`, see `normalize`.
+    pub kind: LinkKind,
+    pub created_at: String,
+}
+
+/// Column list matching `CardLink::from_row`.
+pub(crate) const LINK_COLUMNS: &str = "id, from_card_id, to_card_id, kind, created_at";
+
+impl CardLink {
+    pub(crate) fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let kind: String = row.get(3)?;
+
+        Ok(CardLink {
+            id: row.get(0)?,
+            from_card_id: row.get(1)?,
+            to_card_id: row.get(2)?,
+            kind: LinkKind::parse(&kind).ok_or_else(|| {
+                rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, kind.into())
+            })?,
+            created_at: row.get(4)?,
+        })
+    }
+}
+
+/// A card on the other end of a link, as shown in warnings.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkedCard {
+    pub card_id: String,
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddCardLinkInput {
+    pub from_card_id: String,
+    pub to_card_id: String,
+    pub kind: LinkKind,
+}
+
+/// Rewrites a link into the one form it's stored in: "blocked by" becomes
+/// the reverse "blocks", and "relates to", which has no direction, puts the
+/// smaller id first.
+pub(crate) fn normalize<'a>(from: &'a str, to: &'a str, kind: LinkKind) -> (&'a str, &'a str, LinkKind) {
+    match kind {
+        LinkKind::BlockedBy => (to, from, LinkKind::Blocks),
+        LinkKind::RelatesTo if to < from => (to, from, kind),
+        _ => (from, to, kind),
+    }
+}
+
+/// Whether `card_id` is blocked by `blocker_id`, directly or through a chain
+/// of other blocked cards.
+pub(crate) fn waits_on(conn: &Connection, card_id: &str, blocker_id: &str) -> rusqlite::Result<bool> {
+    conn.query_row(
+        "WITH RECURSIVE blocked(id) AS (
+             SELECT to_card_id FROM card_links WHERE from_card_id = ?1 AND kind = 'blocks'
+             UNION
+             SELECT card_links.to_card_id FROM card_links JOIN blocked ON card_links.from_card_id = blocked.id
+             WHERE card_links.kind = 'blocks'
+         )
+         SELECT EXISTS(SELECT 1 FROM blocked WHERE id = ?2)",
+        [blocker_id, card_id],
+        |row| row.get(0),
+    )
+}
+
+pub(crate) fn insert_link(conn: &Connection, link: &CardLink) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO card_links (id, from_card_id, to_card_id, kind, created_at) VALUES (?, ?, ?, ?, ?)",
+        rusqlite::params![&link.id, &link.from_card_id, &link.to_card_id, link.kind.as_str(), &link.created_at],
+    )?;
+    Ok(())
+}
+
+/// Links two cards. Fails with `DbError::DuplicateLink` if they already are
+/// linked that way, and with `DbError::BlockingCycle` rather than letting two
+/// cards end up waiting on each other.
+pub(crate) fn add_link(
+    conn: &Connection,
+    from_card_id: &str,
+    to_card_id: &str,
+    kind: LinkKind,
+    now: &str,
+) -> Result<CardLink, DbError> {
+    for id in [from_card_id, to_card_id] {
+        if fetch_card(conn, id)?.is_none() {
+            return Err(DbError::NotFound { kind: "card", id: id.to_string() });
+        }
+    }
+
+    let (from, to, kind) = normalize(from_card_id, to_card_id, kind);
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM card_links WHERE from_card_id = ? AND to_card_id = ? AND kind = ?)",
+        [from, to, kind.as_str()],
+        |row| row.get(0),
+    )?;
+    if exists {
+        return Err(DbError::DuplicateLink { from: from.to_string(), to: to.to_string() });
+    }
+    if kind == LinkKind::Blocks && waits_on(conn, from, to)? {
+        return Err(DbError::BlockingCycle { blocker: from.to_string(), blocked: to.to_string() });
+    }
+
+    let link = CardLink {
+        id: Uuid::new_v4().to_string(),
+        from_card_id: from.to_string(),
+        to_card_id: to.to_string(),
+        kind,
+        created_at: now.to_string(),
+    };
+    insert_link(conn, &link)?;
+    Ok(link)
+}
+
+/// Every link to or from a card, leaving out links to trashed cards.
+pub(crate) fn links_for_card(conn: &Connection, card_id: &str) -> rusqlite::Result<Vec<CardLink>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {LINK_COLUMNS}
+         FROM card_links
+         WHERE (from_card_id = ?1 OR to_card_id = ?1)
+           AND NOT EXISTS (
+               SELECT 1 FROM cards
+               WHERE cards.id IN (card_links.from_card_id, card_links.to_card_id) AND cards.deleted_at IS NOT NULL
+           )
+         ORDER BY created_at ASC"
+    ))?;
+
+    let links = stmt
+        .query_map([card_id], CardLink::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(links)
+}
+
+/// Cards blocking `card_id` that aren't finished yet: not in a done column,
+/// archived or trashed.
+pub(crate) fn open_blockers(conn: &Connection, card_id: &str) -> rusqlite::Result<Vec<LinkedCard>> {
+    let mut stmt = conn.prepare(
+        "SELECT cards.id, cards.title
+         FROM card_links
+         JOIN cards ON cards.id = card_links.from_card_id
+         JOIN columns ON columns.id = cards.column_id
+         WHERE card_links.to_card_id = ? AND card_links.kind = 'blocks'
+           AND columns.done = 0 AND cards.archived = 0 AND cards.deleted_at IS NULL
+         ORDER BY cards.title ASC",
+    )?;
+
+    let blockers = stmt
+        .query_map([card_id], |row| Ok(LinkedCard { card_id: row.get(0)?, title: row.get(1)? }))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(blockers)
+}
+
+#[tauri::command]
+pub fn get_card_links(
+    db: tauri::State<'_, Arc<Database>>,
+    card_id: String,
+) -> Result<Vec<CardLink>, AppError> {
+    db.with_connection(|conn| links_for_card(conn, &card_id))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn add_card_link(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    input: AddCardLinkInput,
+) -> Result<CardLink, AppError> {
+    input.validate(&limits)?;
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| add_link(tx, &input.from_card_id, &input.to_card_id, input.kind, &now))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn remove_card_link(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), AppError> {
+    db.with_transaction(|tx| {
+        if tx.execute("DELETE FROM card_links WHERE id = ?", [&id])? == 0 {
+            return Err(DbError::NotFound { kind: "link", id: id.clone() });
+        }
+        Ok(())
+    })
+    .map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cards::{move_between, move_to, CardWarning};
+    use crate::commands::columns::{insert_column, Column};
+    use crate::db::test_helpers::test_helpers::{column, create_test_db, seed_board, seed_card, seed_column};
+
+    /// A board with a "To Do" and a done "Done" column, and three cards in "To Do".
+    fn setup(db: &Database) -> (String, Vec<String>) {
+        db.with_connection(|conn| {
+            let board = seed_board(conn, "Board")?;
+            let todo = seed_column(conn, &board.id, "To Do", 1.0)?;
+            let done = Column { done: true, ..column(&board.id, "Done", 2.0) };
+            insert_column(conn, &done)?;
+
+            let mut card_ids = Vec::new();
+            for title in ["Schema", "API", "UI"] {
+                card_ids.push(seed_card(conn, &todo.id, title, 1.0)?.id);
+            }
+
+            Ok((done.id, card_ids))
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_blocking_cycles_are_rejected() {
+        let (db, _temp) = create_test_db();
+        let (_, cards) = setup(&db);
+        let (schema, api, ui) = (&cards[0], &cards[1], &cards[2]);
+        let now = Utc::now().to_rfc3339();
+
+        db.with_transaction(|tx| add_link(tx, schema, api, LinkKind::Blocks, &now)).unwrap();
+        // Stored the other way round, as "api blocks ui"
+        let link = db.with_transaction(|tx| add_link(tx, ui, api, LinkKind::BlockedBy, &now)).unwrap();
+        assert_eq!((link.from_card_id.as_str(), link.kind), (api.as_str(), LinkKind::Blocks));
+
+        let err = db.with_transaction(|tx| add_link(tx, ui, schema, LinkKind::Blocks, &now)).unwrap_err();
+        assert!(matches!(err, DbError::BlockingCycle { .. }));
+        let err = db.with_transaction(|tx| add_link(tx, schema, ui, LinkKind::BlockedBy, &now)).unwrap_err();
+        assert!(matches!(err, DbError::BlockingCycle { .. }));
+
+        // Other kinds don't form blocking chains
+        db.with_transaction(|tx| add_link(tx, ui, schema, LinkKind::RelatesTo, &now)).unwrap();
+        let err = db.with_transaction(|tx| add_link(tx, schema, ui, LinkKind::RelatesTo, &now)).unwrap_err();
+        assert!(matches!(err, DbError::DuplicateLink { .. }));
+        assert_eq!(AppError::from(err).code(), "validation");
+
+        let links = db.with_connection(|conn| links_for_card(conn, schema)).unwrap();
+        assert_eq!(links.len(), 2);
+        assert_eq!(db.with_connection(|conn| links_for_card(conn, api)).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_moving_into_a_done_column_warns_about_open_blockers() {
+        let (db, _temp) = create_test_db();
+        let (done_id, cards) = setup(&db);
+        let (schema, api) = (&cards[0], &cards[1]);
+        let todo_id = db.with_connection(|conn| fetch_card(conn, api)).unwrap().unwrap().column_id;
+        let now = Utc::now().to_rfc3339();
+
+        db.with_transaction(|tx| add_link(tx, schema, api, LinkKind::Blocks, &now)).unwrap();
+
+        let moved = db.with_transaction(|tx| move_to(tx, api, &done_id, None, 1.0, &now)).unwrap();
+        assert_eq!(
+            moved.warnings,
+            vec![CardWarning::OpenBlockers {
+                blockers: vec![LinkedCard { card_id: schema.clone(), title: "Schema".to_string() }]
+            }]
+        );
+
+        // Drag and drop goes through move_between and warns the same way
+        let moved = db.with_transaction(|tx| move_between(tx, api, &todo_id, None, None, &now)).unwrap().unwrap();
+        assert!(moved.warnings.is_empty());
+        let moved = db.with_transaction(|tx| move_between(tx, api, &done_id, None, None, &now)).unwrap().unwrap();
+        assert!(matches!(moved.warnings.as_slice(), [CardWarning::OpenBlockers { .. }]));
+
+        // Once the blocker is done, nothing is left to warn about
+        let moved = db.with_transaction(|tx| move_to(tx, schema, &done_id, None, 2.0, &now)).unwrap();
+        assert!(moved.warnings.is_empty());
+        assert!(db.with_connection(|conn| open_blockers(conn, api)).unwrap().is_empty());
+    }
+}
@@ -1,4 +1,19 @@
+pub mod activity;
+pub mod attachments;
 pub mod backup;
 pub mod boards;
 pub mod cards;
+pub mod checklists;
 pub mod columns;
+pub mod comments;
+pub mod custom_fields;
+pub mod export;
+pub mod history;
+pub mod import;
+pub mod labels;
+pub mod lanes;
+pub mod links;
+pub mod search;
+pub mod stats;
+pub mod templates;
+pub mod trash;
@@ -0,0 +1,225 @@
+use crate::commands::cards::{Card, CARD_COLUMNS};
+use crate::db::Database;
+use crate::error::AppError;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::sync::Arc;
+
+const MAX_RESULTS: i64 = 50;
+
+/// Placed around matches by FTS5 and turned into `<mark>` tags once the rest
+/// of the text has been escaped. Private-use characters, so they don't clash
+/// with anything a user types.
+const MATCH_START: char = '\u{E000}';
+const MATCH_END: char = '\u{E001}';
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub card: Card,
+    /// Card title as HTML: the text is escaped and matched terms are wrapped
+    /// in `<mark>` tags.
+    pub title_highlight: String,
+    /// Excerpt of the description around the best match, if it has one, as
+    /// HTML like `title_highlight`.
+    pub snippet: Option<String>,
+    /// BM25 score; lower is a better match.
+    pub rank: f64,
+}
+
+/// Turns free-form user input into an FTS5 query. Each whitespace-separated
+/// term is quoted (so stray `"`, `*` or `-` can't cause syntax errors) and
+/// prefix-matched, so results show up while the user is still typing.
+fn to_match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Escapes FTS5 output for use as HTML, turning the match markers into tags.
+fn to_html(marked: &str) -> String {
+    let mut html = String::with_capacity(marked.len());
+    for c in marked.chars() {
+        match c {
+            MATCH_START => html.push_str("<mark>"),
+            MATCH_END => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+pub(crate) fn search(
+    conn: &Connection,
+    query: &str,
+    board_id: Option<&str>,
+) -> rusqlite::Result<Vec<SearchHit>> {
+    let Some(expression) = to_match_expression(query) else {
+        return Ok(Vec::new());
+    };
+
+    let mut stmt = conn.prepare(&format!(
+        r#"SELECT {CARD_COLUMNS}, hits.title_highlight, hits.snippet, hits.rank
+           FROM (
+               SELECT rowid,
+                      highlight(cards_fts, 0, ?4, ?5) AS title_highlight,
+                      snippet(cards_fts, 1, ?4, ?5, '…', 16) AS snippet,
+                      rank
+               FROM cards_fts
+               WHERE cards_fts MATCH ?1
+           ) hits
+           INNER JOIN cards ON cards.rowid = hits.rowid
+           WHERE cards.archived = 0 AND cards.deleted_at IS NULL
+             AND cards.column_id IN (
+                 SELECT col.id FROM columns col
+                 INNER JOIN boards b ON b.id = col.board_id
+                 WHERE col.deleted_at IS NULL AND b.deleted_at IS NULL
+                   AND (?2 IS NULL OR col.board_id = ?2)
+             )
+           ORDER BY hits.rank
+           LIMIT ?3"#
+    ))?;
+
+    let params = rusqlite::params![expression, board_id, MAX_RESULTS, MATCH_START.to_string(), MATCH_END.to_string()];
+    let hits = stmt
+        .query_map(params, |row| {
+            let card = Card::from_row(row)?;
+            let offset = row.as_ref().column_count() - 3;
+            let title: String = row.get(offset)?;
+            let snippet: Option<String> = row.get(offset + 1)?;
+
+            Ok(SearchHit {
+                card,
+                title_highlight: to_html(&title),
+                snippet: snippet.filter(|s| !s.is_empty()).map(|s| to_html(&s)),
+                rank: row.get(offset + 2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(hits)
+}
+
+#[tauri::command]
+pub fn search_cards(
+    db: tauri::State<'_, Arc<Database>>,
+    query: String,
+    board_id: Option<String>,
+) -> Result<Vec<SearchHit>, AppError> {
+    db.with_connection(|conn| search(conn, &query, board_id.as_deref()))
+        .map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cards::insert_card;
+    use crate::db::test_helpers::test_helpers::{card, create_test_db, seed_board, seed_column};
+
+    fn insert_board(conn: &Connection, cards: &[(&str, Option<&str>)]) -> rusqlite::Result<(String, Vec<String>)> {
+        let board = seed_board(conn, "Board")?;
+        let column = seed_column(conn, &board.id, "Column", 1.0)?;
+
+        let mut card_ids = Vec::new();
+        for (i, (title, description)) in cards.iter().enumerate() {
+            let card = Card { description: description.map(str::to_string), ..card(&column.id, title, i as f64) };
+            insert_card(conn, &card)?;
+            card_ids.push(card.id);
+        }
+
+        Ok((board.id, card_ids))
+    }
+
+    #[test]
+    fn test_match_expression_escapes_user_input() {
+        assert_eq!(to_match_expression("  "), None);
+        assert_eq!(to_match_expression("login bug").unwrap(), r#""login"* "bug"*"#);
+        assert_eq!(to_match_expression(r#"say "hi"#).unwrap(), r#""say"* """hi"*"#);
+    }
+
+    #[test]
+    fn test_search_ranks_and_highlights() {
+        let (db, _temp) = create_test_db();
+
+        db.with_connection(|conn| {
+            insert_board(conn, &[
+                ("Fix login redirect", Some("Users land on a blank page after login")),
+                ("Write release notes", Some("Mention the login fix")),
+                ("Update dependencies", None),
+            ])
+        }).unwrap();
+
+        let hits = db.with_connection(|conn| search(conn, "login", None)).unwrap();
+
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].card.title, "Fix login redirect");
+        assert_eq!(hits[0].title_highlight, "Fix <mark>login</mark> redirect");
+        assert!(hits[1].snippet.as_deref().unwrap().contains("<mark>login</mark>"));
+        assert!(hits[0].rank <= hits[1].rank);
+    }
+
+    #[test]
+    fn test_highlights_escape_card_text() {
+        let (db, _temp) = create_test_db();
+
+        db.with_connection(|conn| {
+            insert_board(conn, &[("<img src=x onerror=alert(1)> login", Some("Tom & Jerry's \"login\" page"))])
+        }).unwrap();
+
+        let hits = db.with_connection(|conn| search(conn, "login", None)).unwrap();
+        assert_eq!(hits[0].title_highlight, "&lt;img src=x onerror=alert(1)&gt; <mark>login</mark>");
+        assert_eq!(hits[0].snippet.as_deref().unwrap(), "Tom &amp; Jerry&#39;s &quot;<mark>login</mark>&quot; page");
+    }
+
+    #[test]
+    fn test_search_scoped_to_board() {
+        let (db, _temp) = create_test_db();
+
+        let board_id = db.with_connection(|conn| {
+            let (board_id, _) = insert_board(conn, &[("Deploy staging", None)])?;
+            insert_board(conn, &[("Deploy production", None)])?;
+            Ok(board_id)
+        }).unwrap();
+
+        let all = db.with_connection(|conn| search(conn, "deploy", None)).unwrap();
+        assert_eq!(all.len(), 2);
+
+        let scoped = db.with_connection(|conn| search(conn, "deploy", Some(&board_id))).unwrap();
+        assert_eq!(scoped.len(), 1);
+        assert_eq!(scoped[0].card.title, "Deploy staging");
+    }
+
+    #[test]
+    fn test_index_follows_card_changes() {
+        let (db, _temp) = create_test_db();
+
+        let card_ids = db.with_connection(|conn| {
+            let (_, card_ids) = insert_board(conn, &[("Draft proposal", None), ("Draft budget", None)])?;
+            Ok(card_ids)
+        }).unwrap();
+
+        db.with_connection(|conn| {
+            conn.execute("UPDATE cards SET title = 'Final proposal' WHERE id = ?", [&card_ids[0]])?;
+            conn.execute("DELETE FROM cards WHERE id = ?", [&card_ids[1]])
+        }).unwrap();
+
+        let drafts = db.with_connection(|conn| search(conn, "draft", None)).unwrap();
+        assert!(drafts.is_empty());
+
+        let finals = db.with_connection(|conn| search(conn, "fin", None)).unwrap();
+        assert_eq!(finals.len(), 1);
+        assert_eq!(finals[0].card.id, card_ids[0]);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::commands::columns::columns_for_board;
+use crate::db::Database;
+use crate::error::AppError;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnStats {
+    pub column_id: String,
+    pub column_name: String,
+    pub card_count: i64,
+    /// Cards that have an estimate; the rest are left out of `total_estimate`.
+    pub estimated_count: i64,
+    pub total_estimate: f64,
+}
+
+/// Card counts and story points for each live column on a board, in display
+/// order. Archived and trashed cards aren't counted.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardStats {
+    pub columns: Vec<ColumnStats>,
+    pub card_count: i64,
+    pub total_estimate: f64,
+}
+
+pub(crate) fn board_stats(conn: &Connection, board_id: &str) -> rusqlite::Result<BoardStats> {
+    let mut stmt = conn.prepare(
+        r#"SELECT column_id, COUNT(*), COUNT(estimate), COALESCE(SUM(estimate), 0)
+           FROM cards
+           WHERE column_id IN (SELECT id FROM columns WHERE board_id = ? AND deleted_at IS NULL)
+             AND archived = 0 AND deleted_at IS NULL
+           GROUP BY column_id"#,
+    )?;
+    let mut totals: HashMap<String, (i64, i64, f64)> = stmt
+        .query_map([board_id], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?, row.get(3)?))))?
+        .collect::<Result<_, _>>()?;
+
+    let columns: Vec<ColumnStats> = columns_for_board(conn, board_id)?
+        .into_iter()
+        .map(|column| {
+            let (card_count, estimated_count, total_estimate) = totals.remove(&column.id).unwrap_or_default();
+            ColumnStats {
+                column_id: column.id,
+                column_name: column.name,
+                card_count,
+                estimated_count,
+                total_estimate,
+            }
+        })
+        .collect();
+
+    Ok(BoardStats {
+        card_count: columns.iter().map(|c| c.card_count).sum(),
+        total_estimate: columns.iter().map(|c| c.total_estimate).sum(),
+        columns,
+    })
+}
+
+/// Per-column card counts and estimate sums for a board
+#[tauri::command]
+pub fn get_board_stats(
+    db: tauri::State<'_, Arc<Database>>,
+    board_id: String,
+) -> Result<BoardStats, AppError> {
+    db.with_connection(|conn| board_stats(conn, &board_id))
+        .map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cards::{insert_card, Card, Priority};
+    use crate::db::test_helpers::test_helpers::{card, create_test_db, seed_board, seed_column};
+
+    #[test]
+    fn test_sums_estimates_per_column() {
+        let (db, _temp) = create_test_db();
+
+        let board_id = db.with_connection(|conn| {
+            let board = seed_board(conn, "Board")?;
+            let todo = seed_column(conn, &board.id, "To Do", 1.0)?;
+            seed_column(conn, &board.id, "Done", 2.0)?;
+
+            for (estimate, archived) in [(Some(3.0), false), (Some(5.0), false), (None, false), (Some(8.0), true)] {
+                insert_card(conn, &Card {
+                    archived,
+                    priority: Some(Priority::High),
+                    estimate,
+                    ..card(&todo.id, "Card", 1.0)
+                })?;
+            }
+
+            Ok(board.id)
+        }).unwrap();
+
+        let stats = db.with_connection(|conn| board_stats(conn, &board_id)).unwrap();
+        assert_eq!(stats.columns.len(), 2);
+        assert_eq!(stats.columns[0].column_name, "To Do");
+        assert_eq!(stats.columns[0].card_count, 3);
+        assert_eq!(stats.columns[0].estimated_count, 2);
+        assert_eq!(stats.columns[0].total_estimate, 8.0);
+        assert_eq!(stats.columns[1].card_count, 0);
+        assert_eq!(stats.columns[1].total_estimate, 0.0);
+        assert_eq!((stats.card_count, stats.total_estimate), (3, 8.0));
+    }
+}
@@ -0,0 +1,308 @@
+use crate::commands::boards::Board;
+use crate::commands::export::{build_export, BoardExport};
+use crate::commands::import::{insert_document, remap_ids};
+use crate::db::{Database, DbError};
+use crate::error::AppError;
+use crate::validation::{required_text, Limits};
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Template {
+    pub id: String,
+    pub name: String,
+    /// Whether boards made from this template start with the saved cards.
+    pub include_cards: bool,
+    pub created_at: String,
+}
+
+/// Column list matching `Template::from_row`.
+const TEMPLATE_COLUMNS: &str = "id, name, include_cards, created_at";
+
+impl Template {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Template {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            include_cards: row.get::<_, i32>(2)? != 0,
+            created_at: row.get(3)?,
+        })
+    }
+}
+
+/// Captures a board's layout as a template: its labels, custom fields, live
+/// columns and, if asked, live cards with their checklists and comments.
+/// Archived items, attachments, dates and checklist progress are left out
+/// since they belong to the board's history rather than its shape.
+/// Returns `None` if the board doesn't exist.
+pub(crate) fn save_template(
+    conn: &Connection,
+    board_id: &str,
+    include_cards: bool,
+    now: &str,
+) -> Result<Option<Template>, DbError> {
+    let Some(mut doc) = build_export(conn, board_id)? else {
+        return Ok(None);
+    };
+
+    doc.columns.retain(|column| !column.archived);
+    let column_ids: HashSet<_> = doc.columns.iter().map(|c| c.id.clone()).collect();
+    if include_cards {
+        doc.cards.retain(|card| !card.archived && column_ids.contains(&card.column_id));
+        for card in &mut doc.cards {
+            card.due_at = None;
+            card.start_at = None;
+        }
+    } else {
+        doc.cards.clear();
+    }
+    let card_ids: HashSet<_> = doc.cards.iter().map(|c| c.id.clone()).collect();
+    doc.checklist_items.retain(|item| card_ids.contains(&item.card_id));
+    for item in &mut doc.checklist_items {
+        item.done = false;
+    }
+    doc.comments.retain(|comment| card_ids.contains(&comment.card_id));
+    doc.field_values.retain(|value| card_ids.contains(&value.card_id));
+    doc.links.retain(|link| card_ids.contains(&link.from_card_id) && card_ids.contains(&link.to_card_id));
+    doc.attachments.clear();
+    doc.files = BTreeMap::new();
+
+    let template = Template {
+        id: Uuid::new_v4().to_string(),
+        name: doc.board.name.clone(),
+        include_cards,
+        created_at: now.to_string(),
+    };
+    let document = serde_json::to_string(&doc).map_err(|e| DbError::Io(e.into()))?;
+
+    conn.execute(
+        "INSERT INTO board_templates (id, name, include_cards, document, created_at) VALUES (?, ?, ?, ?, ?)",
+        rusqlite::params![&template.id, &template.name, include_cards as i32, &document, &template.created_at],
+    )?;
+    Ok(Some(template))
+}
+
+pub(crate) fn list(conn: &Connection) -> rusqlite::Result<Vec<Template>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {TEMPLATE_COLUMNS} FROM board_templates ORDER BY name ASC, created_at ASC"
+    ))?;
+
+    let templates = stmt
+        .query_map([], Template::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(templates)
+}
+
+/// A template's saved board as JSON. Returns `None` if the template doesn't exist.
+pub(crate) fn template_document(conn: &Connection, template_id: &str) -> rusqlite::Result<Option<String>> {
+    conn.query_row(
+        "SELECT document FROM board_templates WHERE id = ?",
+        [template_id],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Creates a new board named `name` from a template's saved board, with
+/// fresh ids and timestamps throughout.
+pub(crate) fn instantiate(conn: &Connection, doc: BoardExport, name: &str, now: &str) -> rusqlite::Result<Board> {
+    let mut doc = remap_ids(doc);
+
+    doc.board.name = name.to_string();
+    doc.board.last_opened_at = Some(now.to_string());
+    doc.board.created_at = now.to_string();
+    doc.board.updated_at = now.to_string();
+    for label in &mut doc.labels {
+        label.created_at = now.to_string();
+        label.updated_at = now.to_string();
+    }
+    for column in &mut doc.columns {
+        column.created_at = now.to_string();
+        column.updated_at = now.to_string();
+    }
+    for lane in &mut doc.lanes {
+        lane.created_at = now.to_string();
+        lane.updated_at = now.to_string();
+    }
+    for card in &mut doc.cards {
+        card.created_at = now.to_string();
+        card.updated_at = now.to_string();
+    }
+    for item in &mut doc.checklist_items {
+        item.created_at = now.to_string();
+        item.updated_at = now.to_string();
+    }
+    for comment in &mut doc.comments {
+        comment.created_at = now.to_string();
+        comment.edited_at = None;
+    }
+    for field in &mut doc.custom_fields {
+        field.created_at = now.to_string();
+        field.updated_at = now.to_string();
+    }
+    for value in &mut doc.field_values {
+        value.updated_at = now.to_string();
+    }
+    for link in &mut doc.links {
+        link.created_at = now.to_string();
+    }
+
+    insert_document(conn, &doc)?;
+    Ok(doc.board)
+}
+
+#[tauri::command]
+pub fn save_board_as_template(
+    db: tauri::State<'_, Arc<Database>>,
+    board_id: String,
+    include_cards: bool,
+) -> Result<Template, AppError> {
+    let now = Utc::now().to_rfc3339();
+
+    db.with_transaction(|tx| {
+        save_template(tx, &board_id, include_cards, &now)?
+            .ok_or_else(|| DbError::NotFound { kind: "board", id: board_id.clone() })
+    })
+    .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn list_templates(db: tauri::State<'_, Arc<Database>>) -> Result<Vec<Template>, AppError> {
+    db.with_connection(list).map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn create_board_from_template(
+    db: tauri::State<'_, Arc<Database>>,
+    limits: tauri::State<'_, Limits>,
+    template_id: String,
+    name: String,
+) -> Result<Board, AppError> {
+    required_text("Board name", &name, limits.max_name_len)?;
+    let now = Utc::now().to_rfc3339();
+
+    let document = db
+        .with_connection(|conn| template_document(conn, &template_id))?
+        .ok_or_else(|| AppError::NotFound(format!("Template {} not found", template_id)))?;
+    let doc = serde_json::from_str(&document)
+        .map_err(|e| AppError::Corruption(format!("Template {} is unreadable: {}", template_id, e)))?;
+
+    db.with_transaction(|tx| Ok(instantiate(tx, doc, &name, &now)?))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn delete_template(db: tauri::State<'_, Arc<Database>>, id: String) -> Result<(), AppError> {
+    db.with_connection(|conn| {
+        conn.execute("DELETE FROM board_templates WHERE id = ?", [&id])?;
+        Ok(())
+    })
+    .map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::boards::board_snapshot;
+    use crate::commands::cards::{insert_card, Card};
+    use crate::commands::checklists::{add_item, toggle_item, ChecklistSummary};
+    use crate::commands::columns::{insert_column, Column};
+    use crate::commands::comments::{comments_for_card, insert_comment, Comment};
+    use crate::db::test_helpers::test_helpers::{card, column, create_test_db, seed_board, seed_column, seed_label};
+
+    fn seed_sprint(conn: &Connection) -> rusqlite::Result<String> {
+        let board = seed_board(conn, "Sprint 14")?;
+        let todo = seed_column(conn, &board.id, "To Do", 1.0)?;
+        insert_column(conn, &Column { archived: true, ..column(&board.id, "Old", 2.0) })?;
+        let label = seed_label(conn, &board.id, "Chore")?;
+        let now = Utc::now().to_rfc3339();
+
+        for (title, archived) in [("Retro", false), ("Shipped last sprint", true)] {
+            let card = Card {
+                archived,
+                due_at: Some("2026-01-01T00:00:00+00:00".to_string()),
+                label_ids: vec![label.id.clone()],
+                ..card(&todo.id, title, 1.0)
+            };
+            insert_card(conn, &card)?;
+            let item = add_item(conn, &card.id, "Collect feedback", &now)?;
+            toggle_item(conn, &item.id, &now)?;
+            insert_comment(conn, &Comment {
+                id: Uuid::new_v4().to_string(),
+                card_id: card.id.clone(),
+                body: "Bring snacks".to_string(),
+                created_at: now.clone(),
+                edited_at: None,
+            })?;
+        }
+
+        Ok(board.id)
+    }
+
+    #[test]
+    fn test_template_round_trip() {
+        let (db, _temp) = create_test_db();
+
+        let board_id = db.with_connection(seed_sprint).unwrap();
+        let now = Utc::now().to_rfc3339();
+        let template = db
+            .with_transaction(|tx| save_template(tx, &board_id, true, &now))
+            .unwrap()
+            .unwrap();
+        assert_eq!(template.name, "Sprint 14");
+
+        let listed = db.with_connection(list).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert!(listed[0].include_cards);
+
+        let document = db.with_connection(|conn| template_document(conn, &template.id)).unwrap().unwrap();
+        let doc: BoardExport = serde_json::from_str(&document).unwrap();
+        let board = db.with_transaction(|tx| Ok(instantiate(tx, doc, "Sprint 15", &now)?)).unwrap();
+        assert_ne!(board.id, board_id);
+        assert_eq!(board.name, "Sprint 15");
+
+        let snapshot = db.with_connection(|conn| board_snapshot(conn, &board.id)).unwrap().unwrap();
+        assert_eq!(snapshot.columns.len(), 1);
+        assert_eq!(snapshot.columns[0].name, "To Do");
+
+        let cards = &snapshot.cards_by_column[&snapshot.columns[0].id];
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].title, "Retro");
+        assert!(cards[0].due_at.is_none());
+        assert_eq!(cards[0].label_ids.len(), 1);
+        assert_eq!(cards[0].checklist, ChecklistSummary { done: 0, total: 1 });
+        let comments = db.with_connection(|conn| comments_for_card(conn, &cards[0].id)).unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].body, "Bring snacks");
+
+        // The source board is untouched
+        let source = db.with_connection(|conn| board_snapshot(conn, &board_id)).unwrap().unwrap();
+        assert_eq!(source.board.name, "Sprint 14");
+    }
+
+    #[test]
+    fn test_template_without_cards() {
+        let (db, _temp) = create_test_db();
+
+        let board_id = db.with_connection(seed_sprint).unwrap();
+        let now = Utc::now().to_rfc3339();
+        let template = db
+            .with_transaction(|tx| save_template(tx, &board_id, false, &now))
+            .unwrap()
+            .unwrap();
+
+        let document = db.with_connection(|conn| template_document(conn, &template.id)).unwrap().unwrap();
+        let doc: BoardExport = serde_json::from_str(&document).unwrap();
+        assert!(doc.cards.is_empty());
+        assert_eq!(doc.labels.len(), 1);
+
+        assert!(db.with_transaction(|tx| save_template(tx, "missing", false, &now)).unwrap().is_none());
+        assert!(db.with_connection(|conn| template_document(conn, "missing")).unwrap().is_none());
+    }
+}
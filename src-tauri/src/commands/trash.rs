@@ -0,0 +1,397 @@
+use crate::commands::cards::normalize_timestamp;
+use crate::commands::columns::check_wip_limit;
+use crate::commands::history::{self, Op};
+use crate::db::{Database, DbError};
+use crate::error::AppError;
+use chrono::{Duration, SecondsFormat, Utc};
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Trashed items older than this are purged when the app starts.
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TrashKind {
+    Board,
+    Column,
+    Card,
+}
+
+impl TrashKind {
+    fn table(self) -> &'static str {
+        match self {
+            TrashKind::Board => "boards",
+            TrashKind::Column => "columns",
+            TrashKind::Card => "cards",
+        }
+    }
+
+    fn delete_action(self) -> &'static str {
+        match self {
+            TrashKind::Board => "delete_board",
+            TrashKind::Column => "delete_column",
+            TrashKind::Card => "delete_card",
+        }
+    }
+
+    fn from_table(table: &str) -> Option<Self> {
+        match table {
+            "boards" => Some(TrashKind::Board),
+            "columns" => Some(TrashKind::Column),
+            "cards" => Some(TrashKind::Card),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashItem {
+    pub kind: TrashKind,
+    pub id: String,
+    pub board_id: String,
+    /// Board or column name, or card title.
+    pub name: String,
+    pub deleted_at: String,
+}
+
+impl TrashItem {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let table: String = row.get(0)?;
+        let kind = TrashKind::from_table(&table).ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, table.into())
+        })?;
+
+        Ok(TrashItem {
+            kind,
+            id: row.get(1)?,
+            board_id: row.get(2)?,
+            name: row.get(3)?,
+            deleted_at: row.get(4)?,
+        })
+    }
+}
+
+/// Timestamp format for `deleted_at`, chosen so values compare correctly as strings.
+pub(crate) fn deletion_timestamp() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Moves an item into the trash (`Some`) or back out of it (`None`). Returns
+/// `false` if the item doesn't exist or is already in that state.
+pub(crate) fn set_deleted_at(
+    conn: &Connection,
+    kind: TrashKind,
+    id: &str,
+    deleted_at: Option<&str>,
+) -> rusqlite::Result<bool> {
+    let table = kind.table();
+    let changed = match deleted_at {
+        Some(deleted_at) => conn.execute(
+            &format!("UPDATE {table} SET deleted_at = ? WHERE id = ? AND deleted_at IS NULL"),
+            [deleted_at, id],
+        )?,
+        None => conn.execute(
+            &format!("UPDATE {table} SET deleted_at = NULL WHERE id = ? AND deleted_at IS NOT NULL"),
+            [id],
+        )?,
+    };
+    Ok(changed > 0)
+}
+
+/// Trashes an item and journals it so the deletion can be undone.
+pub(crate) fn move_to_trash(
+    conn: &Connection,
+    kind: TrashKind,
+    id: &str,
+    board_id: &str,
+    summary: &str,
+) -> rusqlite::Result<()> {
+    let deleted_at = deletion_timestamp();
+    if !set_deleted_at(conn, kind, id, Some(&deleted_at))? {
+        return Ok(());
+    }
+
+    history::record(
+        conn,
+        board_id,
+        kind.delete_action(),
+        summary,
+        &[Op::trash(kind, id)],
+        &[Op::untrash(kind, id)],
+    )
+}
+
+pub(crate) fn list(conn: &Connection, board_id: Option<&str>) -> rusqlite::Result<Vec<TrashItem>> {
+    let mut stmt = conn.prepare(
+        r#"SELECT 'boards', id, id, name, deleted_at
+           FROM boards
+           WHERE deleted_at IS NOT NULL AND (?1 IS NULL OR id = ?1)
+           UNION ALL
+           SELECT 'columns', id, board_id, name, deleted_at
+           FROM columns
+           WHERE deleted_at IS NOT NULL AND (?1 IS NULL OR board_id = ?1)
+           UNION ALL
+           SELECT 'cards', c.id, col.board_id, c.title, c.deleted_at
+           FROM cards c
+           INNER JOIN columns col ON col.id = c.column_id
+           WHERE c.deleted_at IS NOT NULL AND (?1 IS NULL OR col.board_id = ?1)
+           ORDER BY 5 DESC"#,
+    )?;
+
+    let items = stmt
+        .query_map([board_id], TrashItem::from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(items)
+}
+
+/// Takes an item out of the trash together with the column and board that
+/// contain it, since it would stay hidden otherwise. Returns `false` if the
+/// item isn't in the trash. A restored card counts against its column's WIP
+/// limit like any other card entering it.
+pub(crate) fn restore(conn: &Connection, kind: TrashKind, id: &str) -> Result<bool, DbError> {
+    let mut chain = vec![(kind, id.to_string())];
+    if kind == TrashKind::Card {
+        let Some(column_id) = conn
+            .query_row("SELECT column_id FROM cards WHERE id = ?", [id], |row| row.get(0))
+            .optional()?
+        else {
+            return Ok(false);
+        };
+        chain.push((TrashKind::Column, column_id));
+    }
+    let card_column = (kind == TrashKind::Card).then(|| chain[1].1.clone());
+    if let Some((TrashKind::Column, column_id)) = chain.last().cloned() {
+        let Some(board_id) = conn
+            .query_row("SELECT board_id FROM columns WHERE id = ?", [&column_id], |row| row.get(0))
+            .optional()?
+        else {
+            return Ok(false);
+        };
+        chain.push((TrashKind::Board, board_id));
+    }
+    let board_id = chain.last().map(|(_, id)| id.clone()).unwrap_or_default();
+
+    let mut trashed = Vec::new();
+    for (kind, id) in chain {
+        let deleted_at: Option<String> = conn
+            .query_row(
+                &format!("SELECT deleted_at FROM {} WHERE id = ?", kind.table()),
+                [&id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        trashed.push((kind, id, deleted_at));
+    }
+
+    // The first entry is the requested item itself
+    if trashed[0].2.is_none() {
+        return Ok(false);
+    }
+
+    let mut forward = Vec::new();
+    let mut inverse = Vec::new();
+    for (kind, id, deleted_at) in trashed {
+        if deleted_at.is_none() {
+            continue;
+        }
+
+        set_deleted_at(conn, kind, &id, None)?;
+        forward.push(Op::untrash(kind, &id));
+        inverse.push(Op::trash(kind, &id));
+    }
+
+    history::record(conn, &board_id, "restore_from_trash", "Restore from trash", &forward, &inverse)?;
+    if let Some(column_id) = card_column {
+        check_wip_limit(conn, &column_id)?;
+    }
+    Ok(true)
+}
+
+/// Permanently deletes trashed items, optionally only those trashed before
+/// `cutoff`. Children go with their parent through the foreign-key cascade,
+/// and journal entries mentioning any deleted row are dropped since they
+/// could never be undone or redone again. Returns the number of trashed
+/// items removed.
+pub(crate) fn purge(conn: &Connection, cutoff: Option<&str>) -> rusqlite::Result<usize> {
+    let ids = conn
+        .prepare(
+            r#"WITH doomed_boards AS (
+                   SELECT id FROM boards WHERE deleted_at IS NOT NULL AND (?1 IS NULL OR deleted_at < ?1)
+               ),
+               doomed_columns AS (
+                   SELECT id FROM columns
+                   WHERE (deleted_at IS NOT NULL AND (?1 IS NULL OR deleted_at < ?1)) OR board_id IN doomed_boards
+               )
+               SELECT id FROM doomed_boards
+               UNION SELECT id FROM doomed_columns
+               UNION SELECT id FROM cards
+               WHERE (deleted_at IS NOT NULL AND (?1 IS NULL OR deleted_at < ?1)) OR column_id IN doomed_columns"#,
+        )?
+        .query_map([cutoff], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    for id in &ids {
+        // Ids are UUIDs, so the quoted id only matches that id's JSON strings
+        conn.execute(
+            "DELETE FROM history WHERE board_id = ?1 OR instr(forward, ?2) > 0 OR instr(inverse, ?2) > 0",
+            [id, &format!("\"{id}\"")],
+        )?;
+    }
+
+    let mut purged = 0;
+    for table in ["cards", "columns", "boards"] {
+        purged += conn.execute(
+            &format!("DELETE FROM {table} WHERE deleted_at IS NOT NULL AND (?1 IS NULL OR deleted_at < ?1)"),
+            [cutoff],
+        )?;
+    }
+
+    Ok(purged)
+}
+
+/// The cutoff before which trashed items are purged automatically.
+pub(crate) fn retention_cutoff() -> String {
+    (Utc::now() - Duration::days(TRASH_RETENTION_DAYS)).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// List trashed boards, columns and cards, most recently deleted first
+#[tauri::command]
+pub fn list_trash(
+    db: tauri::State<'_, Arc<Database>>,
+    board_id: Option<String>,
+) -> Result<Vec<TrashItem>, AppError> {
+    db.with_connection(|conn| list(conn, board_id.as_deref()))
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+pub fn restore_from_trash(
+    db: tauri::State<'_, Arc<Database>>,
+    kind: TrashKind,
+    id: String,
+) -> Result<(), AppError> {
+    let restored = db
+        .with_transaction(|tx| restore(tx, kind, &id))?;
+
+    if !restored {
+        return Err(AppError::NotFound("Item is not in the trash".to_string()));
+    }
+    Ok(())
+}
+
+/// Permanently delete trashed items. With `older_than`, only items trashed
+/// before that timestamp are removed.
+#[tauri::command]
+pub fn empty_trash(
+    db: tauri::State<'_, Arc<Database>>,
+    older_than: Option<String>,
+) -> Result<usize, AppError> {
+    let cutoff = older_than.as_deref().map(normalize_timestamp).transpose()?;
+
+    db.with_transaction(|tx| Ok(purge(tx, cutoff.as_deref())?))
+        .map_err(AppError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::history::{entries_for_board, undo_last};
+    use crate::db::test_helpers::test_helpers::{create_test_db, seed_board_with_card};
+
+    fn seed(conn: &Connection) -> rusqlite::Result<(String, String, String)> {
+        let (board, column, card) = seed_board_with_card(conn)?;
+        Ok((board.id, column.id, card.id))
+    }
+
+    fn card_count(conn: &Connection) -> rusqlite::Result<i32> {
+        conn.query_row("SELECT COUNT(*) FROM cards", [], |row| row.get(0))
+    }
+
+    #[test]
+    fn test_trashing_column_keeps_cards() {
+        let (db, _temp) = create_test_db();
+
+        let (board_id, col_id, card_id) = db.with_connection(seed).unwrap();
+
+        db.with_connection(|conn| move_to_trash(conn, TrashKind::Column, &col_id, &board_id, "Delete column")).unwrap();
+        assert_eq!(db.with_connection(card_count).unwrap(), 1);
+
+        let items = db.with_connection(|conn| list(conn, Some(&board_id))).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].kind, TrashKind::Column);
+
+        // Undo takes the column back out of the trash
+        db.with_transaction(|tx| undo_last(tx, &board_id)).unwrap().unwrap();
+        assert!(db.with_connection(|conn| list(conn, None)).unwrap().is_empty());
+
+        db.with_connection(|conn| move_to_trash(conn, TrashKind::Card, &card_id, &board_id, "Delete card")).unwrap();
+        let items = db.with_connection(|conn| list(conn, Some(&board_id))).unwrap();
+        assert_eq!(items[0].kind, TrashKind::Card);
+        assert_eq!(items[0].board_id, board_id);
+    }
+
+    #[test]
+    fn test_restore_card_restores_its_column() {
+        let (db, _temp) = create_test_db();
+
+        let (board_id, col_id, card_id) = db.with_connection(seed).unwrap();
+
+        db.with_connection(|conn| {
+            move_to_trash(conn, TrashKind::Card, &card_id, &board_id, "Delete card")?;
+            move_to_trash(conn, TrashKind::Column, &col_id, &board_id, "Delete column")
+        }).unwrap();
+
+        assert!(db.with_transaction(|tx| restore(tx, TrashKind::Card, &card_id)).unwrap());
+        assert!(db.with_connection(|conn| list(conn, None)).unwrap().is_empty());
+
+        // Not in the trash anymore
+        assert!(!db.with_transaction(|tx| restore(tx, TrashKind::Card, &card_id)).unwrap());
+    }
+
+    #[test]
+    fn test_purge_respects_cutoff() {
+        let (db, _temp) = create_test_db();
+
+        let (board_id, col_id, _) = db.with_connection(seed).unwrap();
+
+        db.with_connection(|conn| {
+            conn.execute(
+                "UPDATE columns SET deleted_at = '2020-01-01T00:00:00Z' WHERE id = ?",
+                [&col_id],
+            )
+        }).unwrap();
+
+        let purged = db.with_connection(|conn| purge(conn, Some("2019-01-01T00:00:00Z"))).unwrap();
+        assert_eq!(purged, 0);
+
+        let purged = db.with_connection(|conn| purge(conn, Some(&retention_cutoff()))).unwrap();
+        assert_eq!(purged, 1);
+        assert_eq!(db.with_connection(card_count).unwrap(), 0);
+
+        let boards = db.with_connection(|conn| {
+            conn.query_row("SELECT COUNT(*) FROM boards WHERE id = ?", [&board_id], |row| row.get::<_, i32>(0))
+        }).unwrap();
+        assert_eq!(boards, 1);
+    }
+
+    #[test]
+    fn test_purge_forgets_history_of_purged_items() {
+        let (db, _temp) = create_test_db();
+
+        let (board_id, col_id, card_id) = db.with_connection(seed).unwrap();
+
+        db.with_connection(|conn| {
+            move_to_trash(conn, TrashKind::Card, &card_id, &board_id, "Delete card")?;
+            move_to_trash(conn, TrashKind::Column, &col_id, &board_id, "Delete column")
+        }).unwrap();
+        assert_eq!(db.with_connection(|conn| entries_for_board(conn, &board_id)).unwrap().len(), 2);
+
+        db.with_connection(|conn| purge(conn, None)).unwrap();
+        assert!(db.with_connection(|conn| entries_for_board(conn, &board_id)).unwrap().is_empty());
+        assert!(db.with_transaction(|tx| undo_last(tx, &board_id)).unwrap().is_none());
+    }
+}
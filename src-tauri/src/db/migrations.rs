@@ -41,6 +41,258 @@ const MIGRATIONS: &[(&str, &str)] = &[
         CREATE INDEX IF NOT EXISTS idx_cards_column ON cards(column_id, "order");
         CREATE INDEX IF NOT EXISTS idx_boards_last_opened ON boards(last_opened_at);
     "#),
+    ("002_labels", r#"
+        -- Board-scoped label catalog
+        CREATE TABLE IF NOT EXISTS labels (
+            id TEXT PRIMARY KEY NOT NULL,
+            board_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            color TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (board_id) REFERENCES boards(id) ON DELETE CASCADE
+        );
+
+        -- Card <-> label assignments
+        CREATE TABLE IF NOT EXISTS card_labels (
+            card_id TEXT NOT NULL,
+            label_id TEXT NOT NULL,
+            PRIMARY KEY (card_id, label_id),
+            FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE,
+            FOREIGN KEY (label_id) REFERENCES labels(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_labels_board ON labels(board_id);
+        CREATE INDEX IF NOT EXISTS idx_card_labels_label ON card_labels(label_id);
+    "#),
+    ("003_card_dates", r#"
+        ALTER TABLE cards ADD COLUMN due_at TEXT;
+        ALTER TABLE cards ADD COLUMN start_at TEXT;
+
+        CREATE INDEX IF NOT EXISTS idx_cards_due ON cards(due_at) WHERE due_at IS NOT NULL;
+    "#),
+    ("004_card_search", r#"
+        -- Full-text index over card text. Keyed by card id rather than rowid
+        -- because rowids of a table without INTEGER PRIMARY KEY can change on VACUUM.
+        CREATE VIRTUAL TABLE IF NOT EXISTS cards_fts USING fts5(
+            card_id UNINDEXED,
+            title,
+            description,
+            tokenize = 'unicode61 remove_diacritics 2'
+        );
+
+        INSERT INTO cards_fts (card_id, title, description)
+            SELECT id, title, COALESCE(description, '') FROM cards;
+
+        CREATE TRIGGER IF NOT EXISTS cards_fts_insert AFTER INSERT ON cards BEGIN
+            INSERT INTO cards_fts (card_id, title, description)
+                VALUES (new.id, new.title, COALESCE(new.description, ''));
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS cards_fts_delete AFTER DELETE ON cards BEGIN
+            DELETE FROM cards_fts WHERE card_id = old.id;
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS cards_fts_update AFTER UPDATE OF title, description ON cards BEGIN
+            DELETE FROM cards_fts WHERE card_id = old.id;
+            INSERT INTO cards_fts (card_id, title, description)
+                VALUES (new.id, new.title, COALESCE(new.description, ''));
+        END;
+    "#),
+    ("005_history", r#"
+        -- Undo/redo journal. No foreign key on board_id: entries must
+        -- outlive the board so that deleting it can be undone.
+        CREATE TABLE IF NOT EXISTS history (
+            seq INTEGER PRIMARY KEY AUTOINCREMENT,
+            board_id TEXT NOT NULL,
+            action TEXT NOT NULL,
+            summary TEXT NOT NULL,
+            forward TEXT NOT NULL,
+            inverse TEXT NOT NULL,
+            undone INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_history_board ON history(board_id, seq);
+    "#),
+    ("006_trash", r#"
+        -- Deleted items stay in place with a timestamp until the trash is emptied
+        ALTER TABLE boards ADD COLUMN deleted_at TEXT;
+        ALTER TABLE columns ADD COLUMN deleted_at TEXT;
+        ALTER TABLE cards ADD COLUMN deleted_at TEXT;
+    "#),
+    ("007_checklists", r#"
+        CREATE TABLE IF NOT EXISTS checklist_items (
+            id TEXT PRIMARY KEY NOT NULL,
+            card_id TEXT NOT NULL,
+            text TEXT NOT NULL,
+            done INTEGER NOT NULL DEFAULT 0,
+            "order" REAL NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_checklist_items_card ON checklist_items(card_id, "order");
+    "#),
+    ("008_comments_and_activity", r#"
+        CREATE TABLE IF NOT EXISTS card_comments (
+            id TEXT PRIMARY KEY NOT NULL,
+            card_id TEXT NOT NULL,
+            body TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            edited_at TEXT,
+            FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+        );
+
+        -- Written alongside card changes, never edited
+        CREATE TABLE IF NOT EXISTS card_activity (
+            seq INTEGER PRIMARY KEY AUTOINCREMENT,
+            card_id TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            summary TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_card_comments_card ON card_comments(card_id, created_at);
+        CREATE INDEX IF NOT EXISTS idx_card_activity_card ON card_activity(card_id, seq);
+    "#),
+    ("009_attachments", r#"
+        -- File contents live in the attachment store, named by hash
+        CREATE TABLE IF NOT EXISTS card_attachments (
+            id TEXT PRIMARY KEY NOT NULL,
+            card_id TEXT NOT NULL,
+            file_name TEXT NOT NULL,
+            hash TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_card_attachments_card ON card_attachments(card_id);
+        CREATE INDEX IF NOT EXISTS idx_card_attachments_hash ON card_attachments(hash);
+    "#),
+    ("010_board_templates", r#"
+        -- A template holds a board export document, instantiated with fresh ids
+        CREATE TABLE IF NOT EXISTS board_templates (
+            id TEXT PRIMARY KEY NOT NULL,
+            name TEXT NOT NULL,
+            include_cards INTEGER NOT NULL DEFAULT 0,
+            document TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+    "#),
+    ("011_wip_limits", r#"
+        ALTER TABLE columns ADD COLUMN wip_limit INTEGER;
+        ALTER TABLE columns ADD COLUMN wip_policy TEXT NOT NULL DEFAULT 'warn';
+    "#),
+    ("012_lanes", r#"
+        CREATE TABLE IF NOT EXISTS lanes (
+            id TEXT PRIMARY KEY NOT NULL,
+            board_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            "order" REAL NOT NULL,
+            collapsed INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (board_id) REFERENCES boards(id) ON DELETE CASCADE
+        );
+
+        -- Cards fall back to no lane when theirs is deleted
+        ALTER TABLE cards ADD COLUMN lane_id TEXT REFERENCES lanes(id) ON DELETE SET NULL;
+
+        CREATE INDEX IF NOT EXISTS idx_lanes_board ON lanes(board_id, "order");
+        CREATE INDEX IF NOT EXISTS idx_cards_lane ON cards(lane_id);
+    "#),
+    ("013_card_priority_and_estimate", r#"
+        ALTER TABLE cards ADD COLUMN priority TEXT;
+        ALTER TABLE cards ADD COLUMN estimate REAL;
+    "#),
+    ("014_custom_fields", r#"
+        -- Board-defined card fields; options holds the JSON choices of select fields
+        CREATE TABLE IF NOT EXISTS custom_fields (
+            id TEXT PRIMARY KEY NOT NULL,
+            board_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            options TEXT NOT NULL DEFAULT '[]',
+            "order" REAL NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (board_id) REFERENCES boards(id) ON DELETE CASCADE
+        );
+
+        -- One JSON-encoded value per card and field
+        CREATE TABLE IF NOT EXISTS card_field_values (
+            card_id TEXT NOT NULL,
+            field_id TEXT NOT NULL,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (card_id, field_id),
+            FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE,
+            FOREIGN KEY (field_id) REFERENCES custom_fields(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_custom_fields_board ON custom_fields(board_id, "order");
+        CREATE INDEX IF NOT EXISTS idx_card_field_values_field ON card_field_values(field_id);
+    "#),
+    ("015_card_links", r#"
+        ALTER TABLE columns ADD COLUMN done INTEGER NOT NULL DEFAULT 0;
+
+        -- Directed relations between cards. "Blocked by" is stored as the
+        -- reverse "blocks" and "relates to" with the smaller id first, so each
+        -- relation has exactly one row.
+        CREATE TABLE IF NOT EXISTS card_links (
+            id TEXT PRIMARY KEY NOT NULL,
+            from_card_id TEXT NOT NULL,
+            to_card_id TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            UNIQUE (from_card_id, to_card_id, kind),
+            FOREIGN KEY (from_card_id) REFERENCES cards(id) ON DELETE CASCADE,
+            FOREIGN KEY (to_card_id) REFERENCES cards(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_card_links_to ON card_links(to_card_id, kind);
+    "#),
+    ("016_card_search_external_content", r#"
+        -- Rebuild the search index over the cards table itself, keyed by
+        -- rowid, so the triggers find rows by key instead of scanning the
+        -- index. `Database::replace_with` rebuilds it after a restore, since
+        -- VACUUM INTO may renumber rowids in a backup.
+        DROP TRIGGER IF EXISTS cards_fts_insert;
+        DROP TRIGGER IF EXISTS cards_fts_delete;
+        DROP TRIGGER IF EXISTS cards_fts_update;
+        DROP TABLE IF EXISTS cards_fts;
+
+        CREATE VIRTUAL TABLE cards_fts USING fts5(
+            title,
+            description,
+            content = 'cards',
+            content_rowid = 'rowid',
+            tokenize = 'unicode61 remove_diacritics 2'
+        );
+
+        INSERT INTO cards_fts (cards_fts) VALUES ('rebuild');
+
+        CREATE TRIGGER cards_fts_insert AFTER INSERT ON cards BEGIN
+            INSERT INTO cards_fts (rowid, title, description)
+                VALUES (new.rowid, new.title, new.description);
+        END;
+
+        CREATE TRIGGER cards_fts_delete AFTER DELETE ON cards BEGIN
+            INSERT INTO cards_fts (cards_fts, rowid, title, description)
+                VALUES ('delete', old.rowid, old.title, old.description);
+        END;
+
+        CREATE TRIGGER cards_fts_update AFTER UPDATE OF title, description ON cards BEGIN
+            INSERT INTO cards_fts (cards_fts, rowid, title, description)
+                VALUES ('delete', old.rowid, old.title, old.description);
+            INSERT INTO cards_fts (rowid, title, description)
+                VALUES (new.rowid, new.title, new.description);
+        END;
+    "#),
 ];
 
 pub fn run_migrations(conn: &Connection) -> Result<()> {
@@ -99,6 +351,19 @@ mod tests {
         assert!(tables.contains(&"boards".to_string()));
         assert!(tables.contains(&"columns".to_string()));
         assert!(tables.contains(&"cards".to_string()));
+        assert!(tables.contains(&"labels".to_string()));
+        assert!(tables.contains(&"card_labels".to_string()));
+        assert!(tables.contains(&"cards_fts".to_string()));
+        assert!(tables.contains(&"history".to_string()));
+        assert!(tables.contains(&"checklist_items".to_string()));
+        assert!(tables.contains(&"card_comments".to_string()));
+        assert!(tables.contains(&"card_activity".to_string()));
+        assert!(tables.contains(&"card_attachments".to_string()));
+        assert!(tables.contains(&"board_templates".to_string()));
+        assert!(tables.contains(&"lanes".to_string()));
+        assert!(tables.contains(&"custom_fields".to_string()));
+        assert!(tables.contains(&"card_field_values".to_string()));
+        assert!(tables.contains(&"card_links".to_string()));
         assert!(tables.contains(&"_migrations".to_string()));
 
         // Verify migration was recorded
@@ -107,7 +372,7 @@ mod tests {
             stmt.query_row([], |row| row.get::<_, i32>(0))
         }).unwrap();
 
-        assert_eq!(migration_count, 1);
+        assert_eq!(migration_count, MIGRATIONS.len() as i32);
     }
 
     #[test]
@@ -143,6 +408,16 @@ mod tests {
         assert!(indexes.contains(&"idx_columns_board".to_string()));
         assert!(indexes.contains(&"idx_cards_column".to_string()));
         assert!(indexes.contains(&"idx_boards_last_opened".to_string()));
-        assert_eq!(indexes.len(), 3);
+        assert!(indexes.contains(&"idx_labels_board".to_string()));
+        assert!(indexes.contains(&"idx_card_labels_label".to_string()));
+        assert!(indexes.contains(&"idx_cards_due".to_string()));
+        assert!(indexes.contains(&"idx_history_board".to_string()));
+        assert!(indexes.contains(&"idx_checklist_items_card".to_string()));
+        assert!(indexes.contains(&"idx_card_comments_card".to_string()));
+        assert!(indexes.contains(&"idx_card_activity_card".to_string()));
+        assert!(indexes.contains(&"idx_custom_fields_board".to_string()));
+        assert!(indexes.contains(&"idx_card_field_values_field".to_string()));
+        assert!(indexes.contains(&"idx_card_links_to".to_string()));
+        assert_eq!(indexes.len(), 17);
     }
 }
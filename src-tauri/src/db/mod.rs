@@ -5,24 +5,54 @@ pub mod migrations;
 pub mod test_helpers;
 
 use parking_lot::Mutex;
-use rusqlite::Connection;
-use std::path::Path;
+use rusqlite::{Connection, Transaction};
+use std::fs;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum DbError {
     #[error("Database error: {0}")]
     Sqlite(#[from] rusqlite::Error),
-    #[error("Database not initialized")]
-    NotInitialized,
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("{kind} {id} not found")]
+    NotFound { kind: &'static str, id: String },
+    /// A batch stopped at one of its items; the whole batch was rolled back.
+    #[error("Failed to update {kind} {id}: {source}")]
+    ItemFailed {
+        kind: &'static str,
+        id: String,
+        source: rusqlite::Error,
+    },
+    /// A card would take a column with a blocking WIP limit past it.
+    #[error("\"{column}\" is at its WIP limit of {limit}")]
+    WipLimit { column: String, limit: i64 },
+    /// A blocking link would make a card wait on itself.
+    #[error("Card {blocker} can't block card {blocked}: it already waits on it")]
+    BlockingCycle { blocker: String, blocked: String },
+    /// The same link between two cards already exists.
+    #[error("Cards {from} and {to} are already linked")]
+    DuplicateLink { from: String, to: String },
+    /// A card was pointed at a column or lane on another board.
+    #[error("{kind} {id} is on a different board")]
+    WrongBoard { kind: &'static str, id: String },
 }
 
 pub struct Database {
     conn: Mutex<Connection>,
+    path: PathBuf,
 }
 
 impl Database {
     pub fn new(path: &Path) -> Result<Self, DbError> {
+        Ok(Self {
+            conn: Mutex::new(Self::open(path)?),
+            path: path.to_path_buf(),
+        })
+    }
+
+    fn open(path: &Path) -> Result<Connection, DbError> {
         let conn = Connection::open(path)?;
 
         // Enable WAL mode for crash safety and better concurrency
@@ -32,9 +62,7 @@ impl Database {
              PRAGMA busy_timeout = 5000;"
         )?;
 
-        Ok(Self {
-            conn: Mutex::new(conn),
-        })
+        Ok(conn)
     }
 
     pub fn run_migrations(&self) -> Result<(), DbError> {
@@ -50,4 +78,87 @@ impl Database {
         let conn = self.conn.lock();
         f(&conn).map_err(DbError::from)
     }
+
+    /// Runs `f` inside a transaction that commits if it returns `Ok` and rolls
+    /// back otherwise. Helpers called from `f` must not open their own.
+    pub fn with_transaction<F, T>(&self, f: F) -> Result<T, DbError>
+    where
+        F: FnOnce(&Transaction) -> Result<T, DbError>,
+    {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        let value = f(&tx)?;
+        tx.commit()?;
+        Ok(value)
+    }
+
+    /// Writes a consistent, self-contained copy of the database to `dest`
+    /// using `VACUUM INTO`, so writes still sitting in the WAL are included.
+    /// Fails if `dest` already exists and is not empty.
+    pub fn backup_to(&self, dest: &Path) -> Result<(), DbError> {
+        let conn = self.conn.lock();
+        conn.execute("VACUUM INTO ?", [dest.to_string_lossy()])?;
+        Ok(())
+    }
+
+    /// Replaces the live database file with `source` and reopens the connection.
+    /// The lock is held throughout, so no command observes a half-swapped database.
+    /// If the swap or the reopen fails the original file is put back and reopened.
+    pub fn replace_with(&self, source: &Path) -> Result<(), DbError> {
+        let mut conn = self.conn.lock();
+
+        // Stage the copy next to the live file so the final swap is a rename
+        let staged = self.path.with_extension("db-restore");
+        fs::copy(source, &staged)?;
+
+        // Close the current connection so its WAL is folded in and released
+        let old = std::mem::replace(&mut *conn, Connection::open_in_memory()?);
+        if let Err((old, e)) = old.close() {
+            // Keep serving the original database rather than the placeholder
+            *conn = old;
+            let _ = fs::remove_file(&staged);
+            return Err(e.into());
+        }
+
+        // Keep the original until the restored copy has opened, to fall back on
+        let previous = self.path.with_extension("db-previous");
+        match self.swap_in(&staged, &previous) {
+            Ok(restored) => {
+                *conn = restored;
+                let _ = fs::remove_file(&previous);
+                Ok(())
+            }
+            Err(e) => {
+                let _ = fs::remove_file(&staged);
+                if previous.exists() {
+                    Self::remove_sidecars(&self.path);
+                    fs::rename(&previous, &self.path)?;
+                }
+                *conn = Self::open(&self.path)?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Moves the live file to `previous`, renames `staged` into its place and
+    /// opens it, ready for use.
+    fn swap_in(&self, staged: &Path, previous: &Path) -> Result<Connection, DbError> {
+        fs::rename(&self.path, previous)?;
+        Self::remove_sidecars(&self.path);
+        fs::rename(staged, &self.path)?;
+
+        let conn = Self::open(&self.path)?;
+        migrations::run_migrations(&conn)?;
+        // The search index is keyed by rowid, which the backup may not have kept
+        conn.execute("INSERT INTO cards_fts (cards_fts) VALUES ('rebuild')", [])?;
+        Ok(conn)
+    }
+
+    fn remove_sidecars(path: &Path) {
+        for suffix in ["-wal", "-shm"] {
+            let mut sidecar = path.as_os_str().to_owned();
+            sidecar.push(suffix);
+            let _ = fs::remove_file(sidecar);
+        }
+    }
 }
@@ -1,7 +1,16 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 pub mod test_helpers {
+    use crate::commands::boards::{insert_board, Board};
+    use crate::commands::cards::{insert_card, Card};
+    use crate::commands::checklists::ChecklistSummary;
+    use crate::commands::columns::{insert_column, Column, WipPolicy};
+    use crate::commands::labels::{insert_label, Label};
     use crate::db::Database;
+    use chrono::Utc;
+    use rusqlite::Connection;
     use tempfile::NamedTempFile;
+    use uuid::Uuid;
 
     pub fn create_test_db() -> (Database, NamedTempFile) {
         let temp_file = NamedTempFile::new().unwrap();
@@ -9,4 +18,95 @@ pub mod test_helpers {
         db.run_migrations().unwrap();
         (db, temp_file)
     }
+
+    // Tests build rows from these and override fields with struct update
+    // syntax, so new columns only need a default here.
+
+    pub fn board(name: &str) -> Board {
+        let now = Utc::now().to_rfc3339();
+        Board {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            last_opened_at: None,
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+
+    pub fn column(board_id: &str, name: &str, order: f64) -> Column {
+        let now = Utc::now().to_rfc3339();
+        Column {
+            id: Uuid::new_v4().to_string(),
+            board_id: board_id.to_string(),
+            name: name.to_string(),
+            order,
+            archived: false,
+            created_at: now.clone(),
+            updated_at: now,
+            wip_limit: None,
+            wip_policy: WipPolicy::default(),
+            done: false,
+        }
+    }
+
+    pub fn card(column_id: &str, title: &str, order: f64) -> Card {
+        let now = Utc::now().to_rfc3339();
+        Card {
+            id: Uuid::new_v4().to_string(),
+            column_id: column_id.to_string(),
+            title: title.to_string(),
+            description: None,
+            order,
+            archived: false,
+            created_at: now.clone(),
+            updated_at: now,
+            label_ids: Vec::new(),
+            due_at: None,
+            start_at: None,
+            checklist: ChecklistSummary::default(),
+            lane_id: None,
+            priority: None,
+            estimate: None,
+        }
+    }
+
+    pub fn seed_board(conn: &Connection, name: &str) -> rusqlite::Result<Board> {
+        let board = board(name);
+        insert_board(conn, &board)?;
+        Ok(board)
+    }
+
+    pub fn seed_column(conn: &Connection, board_id: &str, name: &str, order: f64) -> rusqlite::Result<Column> {
+        let column = column(board_id, name, order);
+        insert_column(conn, &column)?;
+        Ok(column)
+    }
+
+    pub fn seed_card(conn: &Connection, column_id: &str, title: &str, order: f64) -> rusqlite::Result<Card> {
+        let card = card(column_id, title, order);
+        insert_card(conn, &card)?;
+        Ok(card)
+    }
+
+    pub fn seed_label(conn: &Connection, board_id: &str, name: &str) -> rusqlite::Result<Label> {
+        let now = Utc::now().to_rfc3339();
+        let label = Label {
+            id: Uuid::new_v4().to_string(),
+            board_id: board_id.to_string(),
+            name: name.to_string(),
+            color: "#888888".to_string(),
+            created_at: now.clone(),
+            updated_at: now,
+        };
+        insert_label(conn, &label)?;
+        Ok(label)
+    }
+
+    /// A board with a single column holding a single card.
+    pub fn seed_board_with_card(conn: &Connection) -> rusqlite::Result<(Board, Column, Card)> {
+        let board = seed_board(conn, "Board")?;
+        let column = seed_column(conn, &board.id, "Column", 1.0)?;
+        let card = seed_card(conn, &column.id, "Card", 1.0)?;
+        Ok((board, column, card))
+    }
 }
@@ -0,0 +1,127 @@
+use crate::db::DbError;
+use rusqlite::ErrorCode;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use thiserror::Error;
+
+/// The error every command returns. It reaches the frontend as
+/// `{ code, message }`, where `code` is stable enough to branch on and
+/// `message` is meant for people.
+#[derive(Error, Debug)]
+pub enum AppError {
+    /// The item the command refers to does not exist.
+    #[error("{0}")]
+    NotFound(String),
+    /// The input was rejected before anything was written.
+    #[error("{0}")]
+    Validation(String),
+    /// The request is valid but clashes with the current state, e.g. a stale client.
+    #[error("{0}")]
+    Conflict(String),
+    /// The database refused the write, e.g. a broken reference or duplicate id.
+    #[error("{0}")]
+    Constraint(String),
+    /// Reading or writing storage failed.
+    #[error("{0}")]
+    Io(String),
+    /// The database or a backup file is damaged.
+    #[error("{0}")]
+    Corruption(String),
+    /// A column with a blocking WIP limit is full.
+    #[error("{0}")]
+    WipLimit(String),
+}
+
+impl AppError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound(_) => "notFound",
+            AppError::Validation(_) => "validation",
+            AppError::Conflict(_) => "conflict",
+            AppError::Constraint(_) => "constraint",
+            AppError::Io(_) => "io",
+            AppError::Corruption(_) => "corruption",
+            AppError::WipLimit(_) => "wipLimit",
+        }
+    }
+
+    /// Picks the variant that matches a SQLite failure, keeping `message` as-is.
+    fn from_sqlite(e: &rusqlite::Error, message: String) -> Self {
+        match e {
+            rusqlite::Error::QueryReturnedNoRows => AppError::NotFound(message),
+            rusqlite::Error::SqliteFailure(failure, _) => match failure.code {
+                ErrorCode::ConstraintViolation => AppError::Constraint(message),
+                ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase => AppError::Corruption(message),
+                _ => AppError::Io(message),
+            },
+            _ => AppError::Io(message),
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("AppError", 2)?;
+        error.serialize_field("code", self.code())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.end()
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(e: rusqlite::Error) -> Self {
+        let message = format!("Database error: {}", e);
+        AppError::from_sqlite(&e, message)
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        AppError::Io(format!("I/O error: {}", e))
+    }
+}
+
+impl From<DbError> for AppError {
+    fn from(e: DbError) -> Self {
+        let message = e.to_string();
+        match e {
+            DbError::Sqlite(source) | DbError::ItemFailed { source, .. } => AppError::from_sqlite(&source, message),
+            DbError::Io(_) => AppError::Io(message),
+            DbError::NotFound { .. } => AppError::NotFound(message),
+            DbError::WipLimit { .. } => AppError::WipLimit(message),
+            DbError::BlockingCycle { .. } | DbError::DuplicateLink { .. } | DbError::WrongBoard { .. } => {
+                AppError::Validation(message)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_helpers::test_helpers::create_test_db;
+
+    #[test]
+    fn test_serializes_code_and_message() {
+        let error = AppError::from(DbError::NotFound { kind: "card", id: "abc".to_string() });
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json, serde_json::json!({ "code": "notFound", "message": "card abc not found" }));
+    }
+
+    #[test]
+    fn test_classifies_sqlite_failures() {
+        let (db, _temp) = create_test_db();
+
+        let missing_column = db.with_connection(|conn| {
+            conn.execute(
+                r#"INSERT INTO cards (id, column_id, title, "order", archived, created_at, updated_at) VALUES ('c', 'nope', 'Card', 1.0, 0, '', '')"#,
+                [],
+            )
+        });
+        assert_eq!(AppError::from(missing_column.unwrap_err()).code(), "constraint");
+
+        let no_rows = db.with_connection(|conn| {
+            conn.query_row("SELECT id FROM boards WHERE id = 'nope'", [], |row| row.get::<_, String>(0))
+        });
+        assert_eq!(AppError::from(no_rows.unwrap_err()).code(), "notFound");
+    }
+}
@@ -1,9 +1,12 @@
 mod commands;
 mod db;
+mod error;
+mod validation;
 
 #[cfg(test)]
 mod integration_tests;
 
+use commands::attachments::AttachmentStore;
 use db::Database;
 use std::sync::Arc;
 use tauri::Manager;
@@ -16,23 +19,38 @@ pub fn run() {
             let app_dir = app.path().app_data_dir()?;
             std::fs::create_dir_all(&app_dir)?;
             let db_path = app_dir.join("kanban.db");
+            let existed = db_path.exists();
 
-            // Create backup on startup if database exists
-            if db_path.exists() {
+            let database = Database::new(&db_path)?;
+            let attachments = AttachmentStore::new(app_dir.join("attachments"));
+
+            // Create backup on startup if database exists, before migrations touch it
+            if existed {
                 let backups_dir = app_dir.join("backups");
                 std::fs::create_dir_all(&backups_dir).ok();
 
-                let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
-                let backup_path = backups_dir.join(format!("kanban_startup_{}.db", timestamp));
-                std::fs::copy(&db_path, &backup_path).ok();
+                let backup_path = commands::backup::new_backup_path(&backups_dir, "startup");
+                if let Err(e) = commands::backup::write_verified_backup(&database, &attachments, &backup_path) {
+                    eprintln!("Startup backup failed: {}", e);
+                }
 
-                // Cleanup old backups (keep last 7)
-                cleanup_old_backups(&backups_dir, 7);
+                // Cleanup old backups (keep last 7 of each kind)
+                commands::backup::prune_backups(&backups_dir, 7).ok();
             }
 
-            let database = Database::new(&db_path)?;
             database.run_migrations()?;
 
+            // Purge items that have sat in the trash past the retention period
+            let cutoff = commands::trash::retention_cutoff();
+            if let Err(e) = database.with_transaction(|tx| Ok(commands::trash::purge(tx, Some(&cutoff))?)) {
+                eprintln!("Trash cleanup failed: {}", e);
+            }
+
+            // Delete attachment files nothing refers to any more
+            if let Err(e) = database.with_transaction(|tx| commands::attachments::collect_garbage(tx, &attachments)) {
+                eprintln!("Attachment cleanup failed: {}", e);
+            }
+
             // Check database integrity
             database.with_connection(|conn| {
                 let result: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
@@ -43,11 +61,14 @@ pub fn run() {
             }).ok();
 
             app.manage(Arc::new(database));
+            app.manage(attachments);
+            app.manage(validation::Limits::default());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::boards::get_all_boards,
             commands::boards::get_board,
+            commands::boards::load_board,
             commands::boards::create_board,
             commands::boards::update_board,
             commands::boards::delete_board,
@@ -57,35 +78,75 @@ pub fn run() {
             commands::columns::update_column,
             commands::columns::delete_column,
             commands::columns::reorder_columns,
+            commands::columns::get_archived_columns,
+            commands::columns::unarchive_columns,
             commands::cards::get_cards_for_board,
             commands::cards::get_cards_for_column,
             commands::cards::create_card,
             commands::cards::update_card,
             commands::cards::delete_card,
             commands::cards::move_card,
+            commands::cards::move_card_to_lane,
+            commands::cards::move_card_between,
+            commands::cards::get_overdue_cards,
+            commands::cards::get_cards_due_between,
             commands::cards::batch_update_card_orders,
+            commands::cards::get_archived_cards,
+            commands::cards::unarchive_cards,
+            commands::checklists::get_checklist,
+            commands::checklists::add_checklist_item,
+            commands::checklists::toggle_checklist_item,
+            commands::checklists::reorder_checklist_items,
+            commands::checklists::delete_checklist_item,
+            commands::comments::get_comments,
+            commands::comments::add_comment,
+            commands::comments::update_comment,
+            commands::comments::delete_comment,
+            commands::activity::get_card_activity,
+            commands::attachments::attach_file,
+            commands::attachments::list_attachments,
+            commands::attachments::open_attachment,
+            commands::attachments::remove_attachment,
+            commands::labels::get_labels_for_board,
+            commands::labels::create_label,
+            commands::labels::update_label,
+            commands::labels::delete_label,
+            commands::labels::attach_label,
+            commands::labels::detach_label,
+            commands::lanes::get_lanes_for_board,
+            commands::lanes::create_lane,
+            commands::lanes::reorder_lanes,
+            commands::lanes::set_lane_collapsed,
+            commands::lanes::delete_lane,
+            commands::links::get_card_links,
+            commands::links::add_card_link,
+            commands::links::remove_card_link,
+            commands::search::search_cards,
+            commands::stats::get_board_stats,
+            commands::custom_fields::get_custom_fields,
+            commands::custom_fields::define_custom_field,
+            commands::custom_fields::update_custom_field,
+            commands::custom_fields::delete_custom_field,
+            commands::custom_fields::get_card_field_values,
+            commands::custom_fields::set_card_field_value,
+            commands::export::export_board,
+            commands::history::undo,
+            commands::history::redo,
+            commands::history::get_history,
+            commands::import::import_board,
+            commands::templates::save_board_as_template,
+            commands::templates::list_templates,
+            commands::templates::create_board_from_template,
+            commands::templates::delete_template,
+            commands::trash::list_trash,
+            commands::trash::restore_from_trash,
+            commands::trash::empty_trash,
             commands::backup::create_backup,
             commands::backup::list_backups,
             commands::backup::cleanup_old_backups,
+            commands::backup::restore_backup,
             commands::backup::check_database_integrity,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
-
-fn cleanup_old_backups(backups_dir: &std::path::Path, keep_count: usize) {
-    if let Ok(entries) = std::fs::read_dir(backups_dir) {
-        let mut backups: Vec<_> = entries
-            .filter_map(|e| e.ok())
-            .filter(|e| e.path().extension().map_or(false, |ext| ext == "db"))
-            .collect();
-
-        // Sort by name descending (newest first)
-        backups.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
-
-        // Delete old backups
-        for backup in backups.into_iter().skip(keep_count) {
-            let _ = std::fs::remove_file(backup.path());
-        }
-    }
-}
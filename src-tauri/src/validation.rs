@@ -0,0 +1,420 @@
+use crate::commands::boards::{CreateBoardInput, UpdateBoardInput};
+use crate::commands::cards::{
+    BatchUpdateOrderInput, CreateCardInput, MoveCardInput, MoveCardToLaneInput, PageInput, UpdateCardInput,
+};
+use crate::commands::checklists::{AddChecklistItemInput, ReorderChecklistItemInput};
+use crate::commands::columns::{CreateColumnInput, ReorderColumnInput, UpdateColumnInput};
+use crate::commands::comments::{CreateCommentInput, UpdateCommentInput};
+use crate::commands::custom_fields::{check_options, DefineFieldInput, UpdateFieldInput};
+use crate::commands::export::BoardExport;
+use crate::commands::labels::{CreateLabelInput, UpdateLabelInput};
+use crate::commands::lanes::{CreateLaneInput, ReorderLaneInput};
+use crate::commands::links::AddCardLinkInput;
+use crate::error::AppError;
+
+/// Size limits for user-entered text, counted in characters. Managed as app
+/// state so every command checks against the same values.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// Board, column, lane, label and custom field names.
+    pub max_name_len: usize,
+    pub max_title_len: usize,
+    pub max_description_len: usize,
+    /// Largest file that can be attached to a card, in bytes.
+    pub max_attachment_bytes: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_name_len: 200,
+            max_title_len: 500,
+            max_description_len: 20_000,
+            max_attachment_bytes: 100 * 1024 * 1024,
+        }
+    }
+}
+
+/// Checks an input before a command writes anything.
+pub trait Validate {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError>;
+}
+
+pub(crate) fn required_text(field: &str, value: &str, max_len: usize) -> Result<(), AppError> {
+    if value.trim().is_empty() {
+        return Err(AppError::Validation(format!("{} can't be empty", field)));
+    }
+    optional_text(field, value, max_len)
+}
+
+fn optional_text(field: &str, value: &str, max_len: usize) -> Result<(), AppError> {
+    if value.chars().count() > max_len {
+        return Err(AppError::Validation(format!(
+            "{} is longer than {} characters",
+            field, max_len
+        )));
+    }
+    Ok(())
+}
+
+fn estimate(value: f64) -> Result<(), AppError> {
+    if !value.is_finite() || value < 0.0 {
+        return Err(AppError::Validation("Estimate must be a number of points, zero or more".to_string()));
+    }
+    Ok(())
+}
+
+fn order(field: &str, value: f64) -> Result<(), AppError> {
+    if !value.is_finite() {
+        return Err(AppError::Validation(format!("{} must be a finite number", field)));
+    }
+    Ok(())
+}
+
+impl<T: Validate> Validate for [T] {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        self.iter().try_for_each(|item| item.validate(limits))
+    }
+}
+
+impl Validate for CreateBoardInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        required_text("Board name", &self.name, limits.max_name_len)
+    }
+}
+
+impl Validate for UpdateBoardInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        if let Some(name) = &self.name {
+            required_text("Board name", name, limits.max_name_len)?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for CreateColumnInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        required_text("Column name", &self.name, limits.max_name_len)?;
+        if let Some(value) = self.order {
+            order("Column order", value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for UpdateColumnInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        if let Some(name) = &self.name {
+            required_text("Column name", name, limits.max_name_len)?;
+        }
+        if let Some(value) = self.order {
+            order("Column order", value)?;
+        }
+        if self.wip_limit.is_some_and(|limit| limit < 0) {
+            return Err(AppError::Validation("WIP limit can't be negative".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl Validate for ReorderColumnInput {
+    fn validate(&self, _limits: &Limits) -> Result<(), AppError> {
+        order(&format!("Order of column {}", self.id), self.order)
+    }
+}
+
+impl Validate for CreateCardInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        required_text("Card title", &self.title, limits.max_title_len)?;
+        if let Some(description) = &self.description {
+            optional_text("Card description", description, limits.max_description_len)?;
+        }
+        if let Some(value) = self.order {
+            order("Card order", value)?;
+        }
+        if let Some(value) = self.estimate {
+            estimate(value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for UpdateCardInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        if let Some(title) = &self.title {
+            required_text("Card title", title, limits.max_title_len)?;
+        }
+        if let Some(description) = &self.description {
+            optional_text("Card description", description, limits.max_description_len)?;
+        }
+        if let Some(value) = self.order {
+            order("Card order", value)?;
+        }
+        if let Some(Some(value)) = self.estimate {
+            estimate(value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for MoveCardInput {
+    fn validate(&self, _limits: &Limits) -> Result<(), AppError> {
+        order("Card order", self.order)
+    }
+}
+
+impl Validate for MoveCardToLaneInput {
+    fn validate(&self, _limits: &Limits) -> Result<(), AppError> {
+        order("Card order", self.order)
+    }
+}
+
+impl Validate for BatchUpdateOrderInput {
+    fn validate(&self, _limits: &Limits) -> Result<(), AppError> {
+        order(&format!("Order of card {}", self.id), self.order)
+    }
+}
+
+impl Validate for PageInput {
+    fn validate(&self, _limits: &Limits) -> Result<(), AppError> {
+        if self.offset.is_some_and(|offset| offset < 0) {
+            return Err(AppError::Validation("Page offset can't be negative".to_string()));
+        }
+        if self.limit.is_some_and(|limit| limit < 1) {
+            return Err(AppError::Validation("Page size must be at least 1".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl Validate for AddChecklistItemInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        required_text("Checklist item", &self.text, limits.max_title_len)
+    }
+}
+
+impl Validate for ReorderChecklistItemInput {
+    fn validate(&self, _limits: &Limits) -> Result<(), AppError> {
+        order(&format!("Order of checklist item {}", self.id), self.order)
+    }
+}
+
+impl Validate for CreateCommentInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        required_text("Comment", &self.body, limits.max_description_len)
+    }
+}
+
+impl Validate for UpdateCommentInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        required_text("Comment", &self.body, limits.max_description_len)
+    }
+}
+
+impl Validate for CreateLaneInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        required_text("Lane name", &self.name, limits.max_name_len)?;
+        if let Some(value) = self.order {
+            order("Lane order", value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for ReorderLaneInput {
+    fn validate(&self, _limits: &Limits) -> Result<(), AppError> {
+        order(&format!("Order of lane {}", self.id), self.order)
+    }
+}
+
+impl Validate for AddCardLinkInput {
+    fn validate(&self, _limits: &Limits) -> Result<(), AppError> {
+        if self.from_card_id == self.to_card_id {
+            return Err(AppError::Validation("A card can't be linked to itself".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl Validate for CreateLabelInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        required_text("Label name", &self.name, limits.max_name_len)?;
+        required_text("Label color", &self.color, limits.max_name_len)
+    }
+}
+
+impl Validate for UpdateLabelInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        if let Some(name) = &self.name {
+            required_text("Label name", name, limits.max_name_len)?;
+        }
+        if let Some(color) = &self.color {
+            required_text("Label color", color, limits.max_name_len)?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for DefineFieldInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        required_text("Field name", &self.name, limits.max_name_len)?;
+        check_options(self.kind, &self.options, limits)
+    }
+}
+
+/// Imported documents answer to the same limits as items made in the app.
+impl Validate for BoardExport {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        required_text("Board name", &self.board.name, limits.max_name_len)?;
+        for label in &self.labels {
+            required_text("Label name", &label.name, limits.max_name_len)?;
+            required_text("Label color", &label.color, limits.max_name_len)?;
+        }
+        for column in &self.columns {
+            required_text("Column name", &column.name, limits.max_name_len)?;
+            order(&format!("Order of column {}", column.id), column.order)?;
+            if column.wip_limit.is_some_and(|limit| limit < 0) {
+                return Err(AppError::Validation("WIP limit can't be negative".to_string()));
+            }
+        }
+        for lane in &self.lanes {
+            required_text("Lane name", &lane.name, limits.max_name_len)?;
+            order(&format!("Order of lane {}", lane.id), lane.order)?;
+        }
+        for card in &self.cards {
+            required_text("Card title", &card.title, limits.max_title_len)?;
+            if let Some(description) = &card.description {
+                optional_text("Card description", description, limits.max_description_len)?;
+            }
+            order(&format!("Order of card {}", card.id), card.order)?;
+            if let Some(value) = card.estimate {
+                estimate(value)?;
+            }
+        }
+        for item in &self.checklist_items {
+            required_text("Checklist item", &item.text, limits.max_title_len)?;
+            order(&format!("Order of checklist item {}", item.id), item.order)?;
+        }
+        for comment in &self.comments {
+            required_text("Comment", &comment.body, limits.max_description_len)?;
+        }
+        for field in &self.custom_fields {
+            required_text("Field name", &field.name, limits.max_name_len)?;
+            check_options(field.kind, &field.options, limits)?;
+        }
+        if self.files.values().any(|data| data.len() as u64 > limits.max_attachment_bytes) {
+            return Err(AppError::Validation(format!(
+                "An attached file is larger than {} MB",
+                limits.max_attachment_bytes / (1024 * 1024)
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Options are checked by the command, which knows the field's kind.
+impl Validate for UpdateFieldInput {
+    fn validate(&self, limits: &Limits) -> Result<(), AppError> {
+        if let Some(name) = &self.name {
+            required_text("Field name", name, limits.max_name_len)?;
+        }
+        if let Some(value) = self.order {
+            order("Field order", value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(result: Result<(), AppError>) -> String {
+        match result {
+            Err(AppError::Validation(message)) => message,
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    fn card_input(title: &str) -> CreateCardInput {
+        CreateCardInput {
+            column_id: "col".to_string(),
+            title: title.to_string(),
+            description: None,
+            order: None,
+            due_at: None,
+            start_at: None,
+            lane_id: None,
+            priority: None,
+            estimate: None,
+        }
+    }
+
+    #[test]
+    fn test_rejects_blank_names() {
+        let limits = Limits::default();
+
+        let board = CreateBoardInput { name: "   ".to_string(), preset: None };
+        assert_eq!(message(board.validate(&limits)), "Board name can't be empty");
+
+        let column = UpdateColumnInput { name: Some(String::new()), order: None, archived: None, wip_limit: None, wip_policy: None, done: None };
+        assert_eq!(message(column.validate(&limits)), "Column name can't be empty");
+
+        assert_eq!(message(card_input("").validate(&limits)), "Card title can't be empty");
+        assert!(card_input("Write tests").validate(&limits).is_ok());
+
+        // Leaving a field out of an update is fine
+        let update = UpdateBoardInput { name: None };
+        assert!(update.validate(&limits).is_ok());
+    }
+
+    #[test]
+    fn test_enforces_configured_lengths() {
+        let limits = Limits { max_title_len: 5, max_description_len: 10, ..Limits::default() };
+
+        assert!(card_input("Short").validate(&limits).is_ok());
+        assert_eq!(
+            message(card_input("Longer").validate(&limits)),
+            "Card title is longer than 5 characters"
+        );
+
+        // Counted in characters, not bytes
+        assert!(card_input("ééééé").validate(&limits).is_ok());
+
+        let mut input = card_input("Card");
+        input.description = Some("x".repeat(11));
+        assert_eq!(
+            message(input.validate(&limits)),
+            "Card description is longer than 10 characters"
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_finite_orders() {
+        let limits = Limits::default();
+
+        let column = CreateColumnInput { board_id: "b".to_string(), name: "To Do".to_string(), order: Some(f64::NAN) };
+        assert_eq!(message(column.validate(&limits)), "Column order must be a finite number");
+
+        let mv = MoveCardInput { column_id: "c".to_string(), order: f64::INFINITY };
+        assert!(mv.validate(&limits).is_err());
+
+        let batch = [
+            BatchUpdateOrderInput { id: "a".to_string(), order: 1.0 },
+            BatchUpdateOrderInput { id: "b".to_string(), order: f64::NEG_INFINITY },
+        ];
+        assert_eq!(
+            message(batch[..].validate(&limits)),
+            "Order of card b must be a finite number"
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_paging() {
+        let limits = Limits::default();
+
+        assert!(PageInput::default().validate(&limits).is_ok());
+        assert!(PageInput { offset: Some(-1), limit: None }.validate(&limits).is_err());
+        assert!(PageInput { offset: None, limit: Some(0) }.validate(&limits).is_err());
+    }
+}
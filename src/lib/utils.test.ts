@@ -1,5 +1,5 @@
 import { describe, it, expect } from 'vitest';
-import { calculateOrderBetween, normalizeById, sortByOrder, generateId, now } from './utils';
+import { calculateOrderBetween, normalizeById, sortByOrder, generateId, now, errorMessage } from './utils';
 
 describe('calculateOrderBetween', () => {
   it('should return 1.0 when both before and after are null', () => {
@@ -171,3 +171,18 @@ describe('now', () => {
     expect(Math.abs(now_time.getTime() - date.getTime())).toBeLessThan(1000);
   });
 });
+
+describe('errorMessage', () => {
+  it('should use the message of a backend error', () => {
+    expect(errorMessage({ code: 'notFound', message: 'card abc not found' })).toBe('card abc not found');
+  });
+
+  it('should use the message of a thrown Error', () => {
+    expect(errorMessage(new Error('Network error'))).toBe('Network error');
+  });
+
+  it('should fall back to the string form', () => {
+    expect(errorMessage('plain failure')).toBe('plain failure');
+    expect(errorMessage(null)).toBe('null');
+  });
+});
@@ -53,3 +53,19 @@ export function normalizeById<T extends { id: string }>(
 export function sortByOrder<T extends { order: number }>(items: T[]): T[] {
   return [...items].sort((a, b) => a.order - b.order);
 }
+
+/**
+ * Get a readable message from a rejected command (an AppError) or any other
+ * thrown value
+ */
+export function errorMessage(error: unknown): string {
+  if (
+    typeof error === 'object' &&
+    error !== null &&
+    'message' in error &&
+    typeof error.message === 'string'
+  ) {
+    return error.message;
+  }
+  return String(error);
+}
@@ -42,6 +42,14 @@ describe('boardStore', () => {
       expect(useKanbanStore.getState().isLoading).toBe(false);
     });
 
+    it('should show the message of a backend error', async () => {
+      vi.mocked(invoke).mockRejectedValue({ code: 'io', message: 'Database error: disk I/O error' });
+
+      await useKanbanStore.getState().loadBoards();
+
+      expect(useKanbanStore.getState().error).toBe('Database error: disk I/O error');
+    });
+
     it('should set loading state during load', async () => {
       vi.mocked(invoke).mockImplementation(
         () => new Promise(resolve => {
@@ -64,12 +72,13 @@ describe('boardStore', () => {
 
       vi.mocked(invoke)
         .mockResolvedValueOnce(newBoard)
-        .mockResolvedValueOnce(col1)
-        .mockResolvedValueOnce(col2)
-        .mockResolvedValueOnce(col3);
+        .mockResolvedValueOnce([col1, col2, col3]);
 
       await useKanbanStore.getState().createBoard({ name: 'New Board' });
 
+      expect(invoke).toHaveBeenCalledWith('create_board', {
+        input: { name: 'New Board', preset: 'basic' },
+      });
       const state = useKanbanStore.getState();
       expect(state.boards['new-board']).toBeDefined();
       expect(state.columns['col-1']).toBeDefined();
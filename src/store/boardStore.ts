@@ -12,7 +12,7 @@ import type {
   ColumnView,
 } from '../types';
 import { tauriStorage } from '../lib/tauriStorage';
-import { normalizeById, sortByOrder, calculateOrderBetween } from '../lib/utils';
+import { normalizeById, sortByOrder, calculateOrderBetween, errorMessage } from '../lib/utils';
 
 export interface KanbanStore {
   // State
@@ -98,7 +98,7 @@ export const useKanbanStore = create<KanbanStore>((set, get) => ({
       const boards = await tauriStorage.getAllBoards();
       set({ boards: normalizeById(boards), isLoading: false });
     } catch (error) {
-      set({ error: String(error), isLoading: false });
+      set({ error: errorMessage(error), isLoading: false });
     }
   },
 
@@ -125,26 +125,16 @@ export const useKanbanStore = create<KanbanStore>((set, get) => ({
         isLoading: false,
       }));
     } catch (error) {
-      set({ error: String(error), isLoading: false });
+      set({ error: errorMessage(error), isLoading: false });
     }
   },
 
   createBoard: async (input: CreateBoardInput) => {
     set({ isLoading: true, error: null });
     try {
-      const board = await tauriStorage.createBoard(input);
-
-      // Create default columns
-      const defaultColumns = ['To Do', 'Doing', 'Done'];
-      const columns: Column[] = [];
-      for (let i = 0; i < defaultColumns.length; i++) {
-        const column = await tauriStorage.createColumn({
-          boardId: board.id,
-          name: defaultColumns[i],
-          order: i + 1,
-        });
-        columns.push(column);
-      }
+      // The backend creates the default columns along with the board
+      const board = await tauriStorage.createBoard({ preset: 'basic', ...input });
+      const columns = await tauriStorage.getColumnsForBoard(board.id);
 
       set((state) => ({
         boards: { ...state.boards, [board.id]: board },
@@ -154,7 +144,7 @@ export const useKanbanStore = create<KanbanStore>((set, get) => ({
 
       return board;
     } catch (error) {
-      set({ error: String(error), isLoading: false });
+      set({ error: errorMessage(error), isLoading: false });
       throw error;
     }
   },
@@ -183,7 +173,7 @@ export const useKanbanStore = create<KanbanStore>((set, get) => ({
           boards: { ...state.boards, [id]: previousBoard },
         }));
       }
-      set({ error: String(error) });
+      set({ error: errorMessage(error) });
     }
   },
 
@@ -226,7 +216,7 @@ export const useKanbanStore = create<KanbanStore>((set, get) => ({
         boards: previousBoards,
         columns: previousColumns,
         cards: previousCards,
-        error: String(error),
+        error: errorMessage(error),
       });
     }
   },
@@ -244,7 +234,7 @@ export const useKanbanStore = create<KanbanStore>((set, get) => ({
       }));
       return column;
     } catch (error) {
-      set({ error: String(error) });
+      set({ error: errorMessage(error) });
       throw error;
     }
   },
@@ -278,7 +268,7 @@ export const useKanbanStore = create<KanbanStore>((set, get) => ({
           columns: { ...state.columns, [id]: previousColumn },
         }));
       }
-      set({ error: String(error) });
+      set({ error: errorMessage(error) });
     }
   },
 
@@ -305,7 +295,7 @@ export const useKanbanStore = create<KanbanStore>((set, get) => ({
       set({
         columns: previousColumns,
         cards: previousCards,
-        error: String(error),
+        error: errorMessage(error),
       });
     }
   },
@@ -337,7 +327,7 @@ export const useKanbanStore = create<KanbanStore>((set, get) => ({
     } catch (error) {
       set((state) => ({
         columns: { ...state.columns, [id]: previousColumn },
-        error: String(error),
+        error: errorMessage(error),
       }));
     }
   },
@@ -351,7 +341,7 @@ export const useKanbanStore = create<KanbanStore>((set, get) => ({
       }));
       return card;
     } catch (error) {
-      set({ error: String(error) });
+      set({ error: errorMessage(error) });
       throw error;
     }
   },
@@ -385,7 +375,7 @@ export const useKanbanStore = create<KanbanStore>((set, get) => ({
           cards: { ...state.cards, [id]: previousCard },
         }));
       }
-      set({ error: String(error) });
+      set({ error: errorMessage(error) });
     }
   },
 
@@ -402,7 +392,7 @@ export const useKanbanStore = create<KanbanStore>((set, get) => ({
     try {
       await tauriStorage.deleteCard(id);
     } catch (error) {
-      set({ cards: previousCards, error: String(error) });
+      set({ cards: previousCards, error: errorMessage(error) });
     }
   },
 
@@ -450,7 +440,7 @@ export const useKanbanStore = create<KanbanStore>((set, get) => ({
       // Rollback
       set((state) => ({
         cards: { ...state.cards, [cardId]: previousCard },
-        error: String(error),
+        error: errorMessage(error),
       }));
     }
   },
@@ -31,8 +31,13 @@ export interface Card {
 
 // Input types for creating/updating
 
+/** Starting columns for a new board. */
+export type BoardPreset = 'basic' | 'scrum' | 'none';
+
 export interface CreateBoardInput {
   name: string;
+  /** Leaving it out creates the board without columns. */
+  preset?: BoardPreset;
 }
 
 export interface UpdateBoardInput {
@@ -80,6 +85,17 @@ export interface BatchUpdateOrderInput {
   order: number;
 }
 
+// Errors
+
+/**
+ * What every backend command rejects with. Branch on `code`; `message` is
+ * meant for people.
+ */
+export interface AppError {
+  code: 'notFound' | 'validation' | 'conflict' | 'constraint' | 'io' | 'corruption' | 'wipLimit';
+  message: string;
+}
+
 // Store state types
 
 export interface BoardState {